@@ -0,0 +1,39 @@
+//! The implementations of the `multiconst_proc_macros` macros,
+//! not covered by semver.
+
+use used_proc_macro::TokenStream;
+
+use crate::for_multiconst_macro::Usedwhere;
+
+pub fn multiconst_macro(args: TokenStream) -> TokenStream {
+    crate::for_multiconst_macro::macro_impl(args, Usedwhere::OutsideImpls).unwrap_or_else(|e| e)
+}
+
+pub fn associated_multiconst_macro(args: TokenStream) -> TokenStream {
+    crate::for_multiconst_macro::macro_impl(args, Usedwhere::InherentImpl).unwrap_or_else(|e| e)
+}
+
+pub fn extension_multiconst_macro(args: TokenStream) -> TokenStream {
+    crate::for_multiconst_macro::extension_macro_impl(args).unwrap_or_else(|e| e)
+}
+
+pub fn multiconst_enum_macro(args: TokenStream) -> TokenStream {
+    crate::for_enum_macro::macro_impl(args).unwrap_or_else(|e| e)
+}
+
+pub fn field_macro(args: TokenStream) -> TokenStream {
+    crate::for_field_macros::field_macro_impl(args).unwrap_or_else(|e| e)
+}
+
+pub fn field_path_macro(args: TokenStream) -> TokenStream {
+    crate::for_field_macros::field_path_macro_impl(args).unwrap_or_else(|e| e)
+}
+
+pub fn field_name_aliases_macro(args: TokenStream) -> TokenStream {
+    crate::for_field_macros::field_name_aliases_macro_impl(args).unwrap_or_else(|e| e)
+}
+
+#[cfg(feature = "__derive")]
+pub fn field_type_derive(args: TokenStream) -> TokenStream {
+    crate::derive_macro::derive_macro_impl(args).unwrap_or_else(|e| e.to_compile_error())
+}
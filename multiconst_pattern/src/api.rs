@@ -0,0 +1,230 @@
+//! The public API of this crate, which uses `proc_macro2`.
+
+use proc_macro2::{Ident, TokenStream};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    error::Error,
+    for_multiconst_macro::{self, ConstOutput, Usedwhere},
+    parsing::ParseBuffer,
+    pattern,
+    pattern_processing::{self, ExtractConstCtx, FieldType, WholeFieldPat},
+    syntax::Crate,
+    type_,
+};
+
+impl Error {
+    /// Converts this error into `compile_error` invocations,
+    /// using the `compile_error` macro reexported by the `multiconst` crate.
+    ///
+    /// `multiconst` is the name of the `multiconst` crate in the crate that
+    /// uses the generated code.
+    pub fn into_compile_error(self, multiconst: &Ident) -> TokenStream {
+        self.to_compile_error(&make_crate(multiconst))
+    }
+}
+
+/// A destructuring pattern, eg: `[A, (B, _), C @ ..]`.
+///
+/// This has the same syntax as the patterns in `multiconst` macros.
+pub struct Pattern {
+    pattern: pattern::Pattern,
+}
+
+/// Parses a destructuring pattern, erroring if there are tokens after the pattern.
+pub fn parse_pattern(tokens: TokenStream) -> Result<Pattern, Error> {
+    let input = &mut ParseBuffer::new(tokens);
+    let pattern = pattern::Pattern::parse(input)?;
+    input.assert_empty()?;
+
+    Ok(Pattern { pattern })
+}
+
+/// A [`Pattern`] whose types were inferred from the type of the destructured value,
+/// returned by [`infer_types`].
+pub struct TypedPattern {
+    type_: TokenStream,
+    bindings: Vec<Binding>,
+}
+
+impl TypedPattern {
+    /// The type of the destructured value, with inferred array lengths.
+    pub fn type_(&self) -> &TokenStream {
+        &self.type_
+    }
+
+    /// The constants that the pattern declares, in the order that they're written.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+}
+
+/// A constant that a [`Pattern`] declares.
+pub struct Binding {
+    name: Ident,
+    type_: TokenStream,
+    field_path: String,
+    attrs: TokenStream,
+}
+
+impl Binding {
+    /// The name of the constant.
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    /// The type of the constant.
+    pub fn type_(&self) -> &TokenStream {
+        &self.type_
+    }
+
+    /// The path to the destructured value, eg: `.foo[3]`,
+    /// empty if the constant is the entire value.
+    pub fn field_path(&self) -> &str {
+        &self.field_path
+    }
+
+    /// The `#[...]` attributes on the constant.
+    pub fn attrs(&self) -> &TokenStream {
+        &self.attrs
+    }
+}
+
+/// Infers the types of the constants that `pattern` declares,
+/// from `type_`, the type of the destructured value.
+///
+/// `multiconst` is the name of the `multiconst` crate in the crate that
+/// uses the inferred types.
+pub fn infer_types(
+    multiconst: &Ident,
+    pattern: &Pattern,
+    type_: TokenStream,
+) -> Result<TypedPattern, Error> {
+    let crate_kw = &make_crate(multiconst);
+
+    let input = &mut ParseBuffer::new(type_);
+    let type_ = type_::Type::parse(input)?;
+    input.assert_empty()?;
+    let type_ = pattern_processing::real_type_from(&pattern.pattern, type_)?;
+
+    let mut bats = Vec::new();
+    let mut tuple_rem_lens = Vec::new();
+    pattern_processing::extract_const_names_tys(
+        &pattern.pattern,
+        FieldType::Direct(&type_),
+        WholeFieldPat::No,
+        &mut ExtractConstCtx {
+            bats: &mut bats,
+            tuple_rem_lens: &mut tuple_rem_lens,
+            tuple_rem_pat_const: &Ident::new("__PRIV_MULTICONST_REM_LENS", multiconst.span()),
+            elem_assertions: &mut Vec::new(),
+            checked_locals: &mut Vec::new(),
+            crate_kw,
+            field_markers: None,
+            field_path: String::new(),
+            element_index: None,
+            in_slice: false,
+            is_refutable: false,
+        },
+    )?;
+
+    // the types of the elements would refer to a constant that's only declared by `expand_to`
+    if !tuple_rem_lens.is_empty() {
+        return Err(Error::new(
+            pattern.pattern.spans(),
+            "`infer_types` doesn't support elements after `..` in tuples whose type is not \
+             written as a tuple",
+        ));
+    }
+
+    let bindings = bats
+        .into_iter()
+        .map(|bat| {
+            let mut type_ = TokenStream::new();
+            match &bat.coercion {
+                Some(coercion) => coercion.type_to_token_stream(&mut type_),
+                None => type_.extend(bat.type_.ty),
+            }
+
+            Binding {
+                name: bat.constant,
+                type_,
+                field_path: bat.field_path,
+                attrs: bat.attrs.attrs,
+            }
+        })
+        .collect();
+
+    Ok(TypedPattern {
+        type_: type_.to_tokens(),
+        bindings,
+    })
+}
+
+/// Where the constants that [`expand_to`] outputs are declared.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// In a module, like the `multiconst` macro
+    Module,
+    /// In an inherent impl block, like the `associated_multiconst` macro
+    InherentImpl,
+}
+
+/// Outputs the constants declared by `input` into `out`,
+/// where `input` uses the same syntax as the `multiconst` macro.
+///
+/// `multiconst` is the name of the `multiconst` crate in the crate that
+/// uses the generated code.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst_pattern::{expand_to, Location};
+///
+/// use proc_macro2::{Ident, Span, TokenStream};
+///
+/// let input = "pub const (A, [B, C]): (u8, [u16; _]) = (3, [5, 8]);";
+///
+/// let mut out = TokenStream::new();
+/// expand_to(
+///     &Ident::new("multiconst", Span::call_site()),
+///     Location::Module,
+///     input.parse().unwrap(),
+///     &mut out,
+/// )
+/// .unwrap();
+///
+/// assert!(out.to_string().contains("pub const B : u16"));
+///
+/// ```
+pub fn expand_to(
+    multiconst: &Ident,
+    location: Location,
+    input: TokenStream,
+    out: &mut TokenStream,
+) -> Result<(), Error> {
+    let used_where = match location {
+        Location::Module => Usedwhere::OutsideImpls,
+        Location::InherentImpl => Usedwhere::InherentImpl,
+    };
+
+    let mut output = ConstOutput::new(used_where);
+    for_multiconst_macro::parse_all_constants(
+        &make_crate(multiconst),
+        used_where,
+        &for_multiconst_macro::const_path(used_where),
+        &mut ParseBuffer::new(input),
+        &mut output,
+    )?;
+
+    out.extend(output.items);
+    Ok(())
+}
+
+fn make_crate(multiconst: &Ident) -> Crate {
+    Crate {
+        ident: multiconst.clone(),
+    }
+}
@@ -0,0 +1,384 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use proc_macro2::{Span, TokenStream};
+
+use quote::quote_spanned;
+
+use syn::{Data, Error, Field};
+
+use crate::syntax::{FieldName, Spans};
+
+#[cfg(test)]
+mod testing;
+
+mod attribute_parsing;
+
+use attribute_parsing::FieldCfg;
+
+pub(crate) fn derive_macro_impl(ts: TokenStream) -> Result<TokenStream, Error> {
+    let input = syn::parse2::<syn::DeriveInput>(ts)?;
+    let name = &input.ident;
+
+    let cont_vis = FTVis::new(&input.vis);
+
+    let fields: Vec<AField<'_>> = match &input.data {
+        Data::Struct(struct_) => struct_
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| AField::from_field(i, f, FTVis::new(&f.vis), None))
+            .collect(),
+        // the fields of enums have the same visibility as the enum
+        Data::Enum(enum_) => enum_
+            .variants
+            .iter()
+            .enumerate()
+            .flat_map(|(vi, variant)| {
+                variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, f)| AField::from_field(i, f, cont_vis, Some(vi)))
+            })
+            .collect(),
+        Data::Union(union_) => union_
+            .fields
+            .named
+            .iter()
+            .enumerate()
+            .map(|(i, f)| AField::from_field(i, f, FTVis::new(&f.vis), None))
+            .collect(),
+    };
+
+    let variant_names: Vec<FieldName> = match &input.data {
+        Data::Enum(enum_) => enum_
+            .variants
+            .iter()
+            .map(|v| FieldName::from_ident(&v.ident))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let cfg = attribute_parsing::parse_attributes(&input, &fields)?;
+
+    let krate = &cfg.krate;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut out = TokenStream::new();
+    let mut list_names = Vec::new();
+    let mut list_name_strs = Vec::new();
+    let mut accessors = Vec::new();
+    let mut accessor_impls = Vec::new();
+    let mut used_names = Vec::new();
+    let mut errs: Result<(), Error> = Ok(());
+
+    // references to the fields of packed structs can be unaligned,
+    // so the methods that return references to fields aren't generated for them.
+    let has_accessors = matches!(input.data, Data::Struct(_)) && !is_packed(&input.attrs);
+
+    for FieldCfg {
+        vis_override,
+        rename,
+        aliases,
+        flatten,
+        field,
+    } in &cfg.fields_cfg
+    {
+        let ty = field.ty;
+        let vis = vis_override.unwrap_or(field.vis);
+        let field_vis = field.syn_vis;
+        let span = field.ty_span;
+
+        if matches!(vis_override, Some(FTVis::Priv))
+            || matches!((cont_vis, vis), (FTVis::Pub, FTVis::Priv))
+        {
+            continue;
+        }
+
+        // the name that the deriving type is queried with, to get a field of `ty`.
+        let outer_name = |field_name: &FieldName| match field.variant {
+            Some(vi) => {
+                let variant_name = variant_names[vi].tokens(krate);
+                let field_name = field_name.tokens(krate);
+                quote_spanned!(span=> #krate::VariantField<#variant_name, #field_name>)
+            }
+            None => field_name.tokens(krate),
+        };
+
+        let field_name = rename.as_ref().unwrap_or(&field.name);
+
+        // aliases are additional names for the field, so they're not in `FieldList`
+        let query_names = core::iter::once(field_name).chain(aliases);
+        for (i, query_name) in query_names.enumerate() {
+            check_unique_name(&mut used_names, field.variant, query_name, &mut errs);
+            let name_ts = outer_name(query_name);
+
+            out.extend(quote_spanned! {span=>
+                impl #impl_generics
+                    #krate::FieldType<#name_ts>
+                for #name #ty_generics #where_clause
+                {
+                    type Type = #ty;
+                }
+            });
+
+            if i == 0 {
+                list_names.push(name_ts.clone());
+                list_name_strs.push(field_name_str(query_name));
+            }
+
+            if has_accessors {
+                let accessor = query_name.accessor_ident(span);
+                let member = &field.member;
+                accessors.push(quote_spanned! {span=>
+                    #[doc(hidden)]
+                    #[allow(non_snake_case, dead_code)]
+                    #field_vis const fn #accessor(&self) -> &#ty {
+                        &self.#member
+                    }
+                });
+                accessor_impls.push(name_ts);
+            }
+        }
+
+        for flat_name in flatten {
+            check_unique_name(&mut used_names, field.variant, flat_name, &mut errs);
+
+            let span = flat_name.spans().start;
+            let flat_name_ts = outer_name(flat_name);
+            let inner_name = flat_name.tokens(krate);
+
+            let mut generics = input.generics.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote_spanned!(span=> #ty: #krate::FieldType<#inner_name>));
+            let where_clause = &generics.where_clause;
+
+            out.extend(quote_spanned! {span=>
+                impl #impl_generics
+                    #krate::FieldType<#flat_name_ts>
+                for #name #ty_generics #where_clause
+                {
+                    type Type = #krate::GetFieldType<#ty, #inner_name>;
+
+                    const ASSERT_EXISTS: () =
+                        <#ty as #krate::FieldType<#inner_name>>::ASSERT_EXISTS;
+                }
+            });
+
+            list_names.push(flat_name_ts.clone());
+            list_name_strs.push(field_name_str(flat_name));
+
+            if let (true, Some(read)) = (
+                has_accessors,
+                read_flattened(&input.generics, field, flat_name),
+            ) {
+                let accessor = flat_name.accessor_ident(span);
+                accessors.push(quote_spanned! {span=>
+                    #[doc(hidden)]
+                    #[allow(non_snake_case, dead_code)]
+                    #field_vis const fn #accessor(&self) -> &#krate::GetFieldType<#ty, #inner_name> {
+                        #read
+                    }
+                });
+                accessor_impls.push(flat_name_ts);
+            }
+        }
+    }
+
+    errs?;
+
+    // the methods that the `multiconst` macros use to read fields named by string literals,
+    // and the `FieldAccessor` impls that the macros assert to error clearly without them.
+    if !accessors.is_empty() {
+        let accessors = quote::quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#accessors)*
+            }
+
+            #(
+                impl #impl_generics #krate::__::FieldAccessor<#accessor_impls>
+                for #name #ty_generics #where_clause
+                {}
+            )*
+        };
+
+        // const fns with trait bounds require Rust 1.61.0
+        if has_trait_bounds(&input.generics) {
+            out.extend(quote::quote!(#krate::__::if_rust_1_61!{ #accessors }));
+        } else {
+            out.extend(accessors);
+        }
+    }
+
+    // enums don't implement `FieldList`, because their fields depend on the variant
+    if !matches!(input.data, Data::Enum(_)) {
+        out.extend(quote::quote! {
+            impl #impl_generics #krate::FieldList for #name #ty_generics #where_clause {
+                type Names = (#(#list_names,)*);
+
+                const FIELD_NAMES: &'static [&'static #krate::__::str] = &[#(#list_name_strs,)*];
+            }
+        });
+    }
+
+    Ok(out)
+}
+
+// The expression that reads the `flat_name` field of `field`,
+// or `None` if it can't be read in a const fn.
+fn read_flattened(
+    generics: &syn::Generics,
+    field: &AField<'_>,
+    flat_name: &FieldName,
+) -> Option<TokenStream> {
+    let span = flat_name.spans().start;
+    let member = &field.member;
+
+    match (field.ty, flat_name) {
+        (syn::Type::Tuple(_), FieldName::Numeric(n, _)) => {
+            let index = syn::Index {
+                index: *n as u32,
+                span,
+            };
+            Some(quote_spanned!(span=> &self.#member.#index))
+        }
+        (syn::Type::Array(_), FieldName::Numeric(n, _)) => {
+            Some(quote_spanned!(span=> &self.#member[#n]))
+        }
+        // type parameters don't have the methods that read the fields
+        (syn::Type::Path(path), _)
+            if path.qself.is_none()
+                && generics
+                    .type_params()
+                    .any(|param| path.path.is_ident(&param.ident)) =>
+        {
+            None
+        }
+        _ => {
+            let accessor = flat_name.accessor_ident(span);
+            Some(quote_spanned!(span=> self.#member.#accessor()))
+        }
+    }
+}
+
+fn is_packed(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => meta.path().is_ident("packed"),
+            syn::NestedMeta::Lit(_) => false,
+        })
+}
+
+fn has_trait_bounds(generics: &syn::Generics) -> bool {
+    generics.type_params().any(|param| !param.bounds.is_empty())
+        || generics
+            .where_clause
+            .iter()
+            .any(|wc| !wc.predicates.is_empty())
+}
+
+// errors if `name` was already used for a field of the same struct or enum variant,
+// since it'd be the name of multiple impls of `FieldType`.
+fn check_unique_name(
+    used_names: &mut Vec<(Option<usize>, String, Span)>,
+    variant: Option<usize>,
+    name: &FieldName,
+    errs: &mut Result<(), Error>,
+) {
+    let name_str = field_name_str(name);
+    let span = name.spans().start;
+
+    let prev = used_names
+        .iter()
+        .find(|(prev_variant, prev_name, _)| *prev_variant == variant && *prev_name == name_str);
+
+    match prev {
+        Some((_, _, prev_span)) => {
+            let mut err = Error::new(span, format!("the `{}` field name is used twice", name_str));
+            err.combine(Error::new(
+                *prev_span,
+                format!("the `{}` field name is first used here", name_str),
+            ));
+
+            match errs {
+                Ok(()) => *errs = Err(err),
+                Err(errs) => errs.combine(err),
+            }
+        }
+        None => used_names.push((variant, name_str, span)),
+    }
+}
+
+fn field_name_str(field_name: &FieldName) -> String {
+    match field_name {
+        FieldName::Numeric(n, _) => n.to_string(),
+        FieldName::Alphabetic(name, _) => name.to_string(),
+        FieldName::NumericConst { .. } => unreachable!("not used in derives"),
+    }
+}
+
+#[derive(Copy, Clone)]
+enum FTVis {
+    Pub,
+    Priv,
+}
+
+impl FTVis {
+    fn new(vis: &syn::Visibility) -> Self {
+        match vis {
+            syn::Visibility::Public { .. } => FTVis::Pub,
+            _ => FTVis::Priv,
+        }
+    }
+}
+
+struct AField<'a> {
+    name: FieldName,
+    /// The name used to access the field, eg: `foo` in `self.foo`
+    member: syn::Member,
+    attrs: &'a [syn::Attribute],
+    vis: FTVis,
+    syn_vis: &'a syn::Visibility,
+    ty: &'a syn::Type,
+    ty_span: Span,
+    /// The index of the variant that the field is in, if it's an enum field.
+    variant: Option<usize>,
+}
+
+impl<'a> AField<'a> {
+    fn from_field(i: usize, f: &'a Field, vis: FTVis, variant: Option<usize>) -> Self {
+        let ty_span = syn::spanned::Spanned::span(&f.ty);
+        Self {
+            name: match &f.ident {
+                Some(x) => FieldName::from_ident(x),
+                None => FieldName::Numeric(i, Spans::from_one(ty_span)),
+            },
+            member: match &f.ident {
+                Some(x) => syn::Member::Named(x.clone()),
+                None => syn::Member::Unnamed(syn::Index {
+                    index: i as u32,
+                    span: ty_span,
+                }),
+            },
+            attrs: &f.attrs,
+            vis,
+            syn_vis: &f.vis,
+            ty: &f.ty,
+            ty_span,
+            variant,
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use super::{AField, FTVis};
 
+use crate::syntax::{FieldName, Spans};
+
 use alloc::vec::Vec;
 
 use proc_macro2::TokenStream;
 
 use syn::{
+    ext::IdentExt,
     parse::{ParseBuffer, ParseStream, Parser},
     Attribute, Error, Token,
 };
@@ -17,11 +20,19 @@ pub(super) struct Config<'a> {
 
 pub(super) struct FieldCfg<'a> {
     pub(super) vis_override: Option<FTVis>,
+    /// The name that the field is queried with, instead of its Rust name.
+    pub(super) rename: Option<FieldName>,
+    /// Additional names that the field can be queried with.
+    pub(super) aliases: Vec<FieldName>,
+    /// The fields of the field's type that are queryable from the deriving type.
+    pub(super) flatten: Vec<FieldName>,
     pub(super) field: &'a AField<'a>,
 }
 
 enum ParseCtx<'a, 'b> {
     Container(&'a mut Config<'b>),
+    /// An enum variant, with the visibility override for its fields
+    Variant(&'a mut Option<FTVis>),
     Field(&'a mut FieldCfg<'b>),
 }
 
@@ -39,9 +50,31 @@ pub(super) fn parse_attributes<'a>(
 
     parse_attributes_outer(&mut errs, &input.attrs, &mut ParseCtx::Container(&mut cfg));
 
+    let variant_vis_overrides: Vec<Option<FTVis>> = match &input.data {
+        syn::Data::Enum(enum_) => enum_
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut vis_override = cfg.vis_override;
+                parse_attributes_outer(
+                    &mut errs,
+                    &variant.attrs,
+                    &mut ParseCtx::Variant(&mut vis_override),
+                );
+                vis_override
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
     for field in fields.iter() {
         let mut field_cfg = FieldCfg {
-            vis_override: cfg.vis_override,
+            vis_override: field
+                .variant
+                .map_or(cfg.vis_override, |vi| variant_vis_overrides[vi]),
+            rename: None,
+            aliases: Vec::new(),
+            flatten: Vec::new(),
             field,
         };
 
@@ -99,22 +132,49 @@ fn parse_attributes_inner(
             ParseCtx::Container(cfg) => {
                 cfg.krate = krate;
             }
-            ParseCtx::Field { .. } => {
+            ParseCtx::Variant { .. } | ParseCtx::Field { .. } => {
                 unreachable!();
             }
         }
     } else if let Some(_) = lookahead.peek_parse(Token!(pub))? {
         let vo = match pctx {
             ParseCtx::Container(x) => &mut x.vis_override,
+            ParseCtx::Variant(x) => x,
             ParseCtx::Field(x) => &mut x.vis_override,
         };
         *vo = Some(FTVis::Pub);
     } else if let Some(_) = lookahead.peek_parse(Token!(priv))? {
         let vo = match pctx {
             ParseCtx::Container(x) => &mut x.vis_override,
+            ParseCtx::Variant(x) => x,
             ParseCtx::Field(x) => &mut x.vis_override,
         };
         *vo = Some(FTVis::Priv);
+    } else if let ParseCtx::Field(field_cfg) = pctx {
+        parse_field_attribute(field_cfg, lookahead, input)?;
+    } else {
+        return Err(lookahead.error());
+    }
+
+    Ok(())
+}
+
+// the attributes that can only be used on fields,
+// only peeked on fields so that errors elsewhere don't list them as expected.
+fn parse_field_attribute(
+    field_cfg: &mut FieldCfg<'_>,
+    lookahead: Lookhead<'_>,
+    input: ParseStream<'_>,
+) -> Result<(), Error> {
+    if lookahead.peek_parse(kw::rename)?.is_some() {
+        field_cfg.rename = Some(parse_field_name_str(input)?);
+    } else if lookahead.peek_parse(kw::alias)?.is_some() {
+        field_cfg.aliases.push(parse_field_name_str(input)?);
+    } else if lookahead.peek_parse(kw::flatten)?.is_some() {
+        let content;
+        syn::parenthesized!(content in input);
+        let names = content.parse_terminated::<_, Token!(,)>(parse_field_name)?;
+        field_cfg.flatten.extend(names);
     } else {
         return Err(lookahead.error());
     }
@@ -122,6 +182,39 @@ fn parse_attributes_inner(
     Ok(())
 }
 
+fn parse_field_name(input: ParseStream<'_>) -> Result<FieldName, Error> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(syn::Ident::peek_any) {
+        syn::Ident::parse_any(input).map(|x| FieldName::from_ident(&x))
+    } else if lookahead.peek(syn::LitInt) {
+        let lit = input.parse::<syn::LitInt>()?;
+        let n = lit.base10_parse::<usize>()?;
+        Ok(FieldName::Numeric(n, Spans::from_one(lit.span())))
+    } else {
+        Err(lookahead.error())
+    }
+}
+
+/// Parses `= "name"`, where `name` is a field name.
+fn parse_field_name_str(input: ParseStream<'_>) -> Result<FieldName, Error> {
+    input.parse::<Token!(=)>()?;
+    let lit = input.parse::<syn::LitStr>()?;
+    lit.parse_with(|input: ParseStream<'_>| {
+        let name = parse_field_name(input)?;
+        if input.is_empty() {
+            Ok(name)
+        } else {
+            Err(input.error("expected a single field name"))
+        }
+    })
+}
+
+mod kw {
+    syn::custom_keyword!(alias);
+    syn::custom_keyword!(flatten);
+    syn::custom_keyword!(rename);
+}
+
 struct Lookhead<'a> {
     input: ParseStream<'a>,
     lookahead: syn::parse::Lookahead1<'a>,
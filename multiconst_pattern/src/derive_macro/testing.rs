@@ -0,0 +1,371 @@
+use used_proc_macro::TokenStream;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+};
+
+use crate::test_utils::StrExt;
+
+fn parse_derive(s: &str) -> Result<String, String> {
+    std::println!("\ninput: \n{}\n", s);
+    let input = s.parse::<TokenStream>().unwrap();
+
+    crate::derive_macro::derive_macro_impl(input)
+        .map(|x| x.to_string())
+        .map_err(|e| e.to_compile_error().to_string())
+}
+
+fn braced_derive(
+    container_annots: &str,
+    field0_annots: &str,
+    field1_annots: &str,
+) -> Result<String, String> {
+    parse_derive(&format!(
+        "
+            {}
+            struct Foo {{
+                {}
+                x: u32,
+                {}
+                y: u64,
+            }}
+        ",
+        container_annots, field0_annots, field1_annots,
+    ))
+}
+
+#[test]
+fn visibility_overrides() {
+    for vis in vec!["", "pub", "pub(crate)"] {
+        {
+            let res = braced_derive(&format!("#[field_type(pub)] {}", vis), "", "").unwrap();
+            assert!(res.consecutive_unspace(&["Type = u32"]), "{}", res);
+            assert!(res.consecutive_unspace(&["Type = u64"]), "{}", res);
+        }
+        {
+            let res = braced_derive(&format!("#[field_type(priv)] {}", vis), "", "").unwrap();
+            assert!(!res.consecutive_unspace(&["Type = u32"]), "{}", res);
+            assert!(!res.consecutive_unspace(&["Type = u64"]), "{}", res);
+        }
+    }
+}
+
+#[test]
+fn visibility_dependence() {
+    for (type_vis, field_vis) in vec![
+        ("", ""),
+        ("pub(crate)", ""),
+        ("", "pub(crate)"),
+        ("", "pub"),
+        ("pub(crate)", "pub"),
+        ("pub", "pub"),
+    ] {
+        let res = braced_derive(type_vis, field_vis, field_vis).unwrap();
+        assert!(res.consecutive_unspace(&["Type = u32"]), "{}", res);
+        assert!(res.consecutive_unspace(&["Type = u64"]), "{}", res);
+    }
+
+    for (type_vis, field_vis) in vec![("pub", ""), ("pub", "pub(crate)")] {
+        let res = braced_derive(type_vis, field_vis, field_vis).unwrap();
+        assert!(!res.consecutive_unspace(&["Type = u32"]), "{}", res);
+        assert!(!res.consecutive_unspace(&["Type = u64"]), "{}", res);
+    }
+}
+
+#[test]
+fn enum_fields() {
+    let res = parse_derive(
+        "
+            pub enum Foo {
+                Bar { x: u32 },
+                #[field_type(priv)]
+                Baz(u64, #[field_type(pub)] u8),
+                Qux,
+            }
+        ",
+    )
+    .unwrap();
+
+    assert!(
+        res.consecutive_unspace(&["VariantField<", "'B', 'a', 'r'", "'x'", "Type = u32"]),
+        "{}",
+        res
+    );
+    assert!(
+        res.consecutive_unspace(&["VariantField<", "'B', 'a', 'z'", "Usize<1>", "Type = u8"]),
+        "{}",
+        res
+    );
+    assert!(!res.consecutive_unspace(&["Type = u64"]), "{}", res);
+    assert_eq!(res.matches("impl").count(), 2, "{}", res);
+}
+
+#[test]
+fn union_fields() {
+    let res = parse_derive(
+        "
+            pub union Foo {
+                pub x: u32,
+                y: u64,
+                #[field_type(pub)]
+                z: u8,
+            }
+        ",
+    )
+    .unwrap();
+
+    assert!(res.consecutive_unspace(&["'x'", "Type = u32"]), "{}", res);
+    assert!(!res.consecutive_unspace(&["Type = u64"]), "{}", res);
+    assert!(res.consecutive_unspace(&["'z'", "Type = u8"]), "{}", res);
+}
+
+#[test]
+fn field_list() {
+    {
+        let res = braced_derive("pub", "pub", "").unwrap();
+        assert!(
+            res.consecutive_unspace(&["FieldList for Foo", "'x'", "FIELD_NAMES", "[\"x\",]"]),
+            "{}",
+            res
+        );
+    }
+    {
+        let res = parse_derive("struct Foo(u8, #[field_type(priv)] u16, u32);").unwrap();
+        assert!(
+            res.consecutive_unspace(&["Names = (", "Usize<0>", "Usize<2>", "[\"0\", \"2\",]"]),
+            "{}",
+            res
+        );
+    }
+    {
+        let res = parse_derive("enum Foo { Bar(u8) }").unwrap();
+        assert!(!res.contains("FieldList"), "{}", res);
+    }
+}
+
+#[test]
+fn flatten_fields() {
+    {
+        let res = braced_derive("", "#[field_type(flatten(port, 0))]", "").unwrap();
+        assert!(
+            res.consecutive_unspace(&[
+                "'p'",
+                "'o'",
+                "'r'",
+                "'t'",
+                "where u32 : multiconst :: FieldType",
+                "Type = multiconst :: GetFieldType < u32",
+                "ASSERT_EXISTS",
+            ]),
+            "{}",
+            res
+        );
+        assert!(
+            res.consecutive_unspace(&["Usize < 0 >", "where u32 :", "Usize < 0 >"]),
+            "{}",
+            res
+        );
+        assert!(
+            res.consecutive_unspace(&["FIELD_NAMES", "[\"x\", \"port\", \"0\", \"y\",]"]),
+            "{}",
+            res
+        );
+    }
+    {
+        let res = braced_derive("", "", "#[field_type(priv)] #[field_type(flatten(z))]").unwrap();
+        assert!(!res.contains("'z'"), "{}", res);
+    }
+    {
+        let res = parse_derive("struct Foo<T: Copy>(#[field_type(flatten(a))] T);").unwrap();
+        assert!(
+            res.consecutive_unspace(&["where T : multiconst :: FieldType"]),
+            "{}",
+            res
+        );
+    }
+    for attr in &[
+        "#[field_type(flatten(a))] struct Foo;",
+        "enum Foo { #[field_type(flatten(a))] Bar }",
+    ] {
+        let err = parse_derive(attr).unwrap_err();
+        assert!(err.contains("expected"), "{}", err);
+        assert!(!err.contains("flatten"), "{}", err);
+    }
+}
+
+#[test]
+fn field_accessors() {
+    {
+        let res = braced_derive(
+            "",
+            "#[field_type(rename = \"z\")] #[field_type(flatten(port, 0))]",
+            "#[field_type(priv)]",
+        )
+        .unwrap();
+        assert!(
+            res.consecutive_unspace(&[
+                "const fn __multiconst_field_z(&self) -> &u32 { &self.x }",
+                "const fn __multiconst_field_port(&self)",
+                "{ self.x.__multiconst_field_port() }",
+                "const fn __multiconst_field_0(&self)",
+                "{ self.x.__multiconst_field_0() }",
+            ]),
+            "{}",
+            res
+        );
+        assert!(
+            res.consecutive_unspace(&[
+                "impl multiconst::__::FieldAccessor<multiconst::__::TIdent<",
+                "> for Foo {}",
+            ]),
+            "{}",
+            res
+        );
+        assert!(!res.contains("__multiconst_field_x"), "{}", res);
+        assert!(!res.contains("__multiconst_field_y"), "{}", res);
+    }
+    {
+        let res = parse_derive(
+            "struct Foo<T> {
+                #[field_type(flatten(1))] pub a: (u8, u16),
+                #[field_type(flatten(2))] b: [T; 3],
+                #[field_type(flatten(x))] c: T,
+            }",
+        )
+        .unwrap();
+        assert!(
+            res.consecutive_unspace(&[
+                "impl<T> Foo<T> {",
+                "pub const fn __multiconst_field_a(&self) -> &(u8, u16) { &self.a }",
+                "pub const fn __multiconst_field_1(&self)",
+                "{ &self.a.1 }",
+                "const fn __multiconst_field_b(&self) -> &[T; 3] { &self.b }",
+                "const fn __multiconst_field_2(&self)",
+                "{ &self.b[2usize] }",
+                "const fn __multiconst_field_c(&self) -> &T { &self.c }",
+            ]),
+            "{}",
+            res
+        );
+        assert!(!res.contains("__multiconst_field_x"), "{}", res);
+    }
+    {
+        let res = parse_derive("struct Foo<T: Copy>(T);").unwrap();
+        assert!(
+            res.consecutive_unspace(&["multiconst::__::if_rust_1_61!{", "impl<T: Copy> Foo<T>"]),
+            "{}",
+            res
+        );
+    }
+    for input in &[
+        "enum Foo { Bar { x: u8 } }",
+        "union Foo { x: u8 }",
+        "struct Foo;",
+        "#[repr(packed)] struct Foo { x: u8 }",
+        "#[repr(C, packed(2))] struct Foo { #[field_type(rename = \"y\")] x: u8 }",
+    ] {
+        let res = parse_derive(input).unwrap();
+        assert!(!res.contains("__multiconst_field"), "{}", res);
+        assert!(!res.contains("FieldAccessor"), "{}", res);
+    }
+}
+
+#[test]
+fn renamed_fields() {
+    {
+        let res = braced_derive("", "#[field_type(rename = \"z\")]", "").unwrap();
+        assert!(res.consecutive_unspace(&["'z'", "Type = u32"]), "{}", res);
+        // renamed fields aren't queryable by their Rust name
+        assert!(!res.contains("'x'"), "{}", res);
+        assert!(
+            res.consecutive_unspace(&["FIELD_NAMES", "[\"z\", \"y\",]"]),
+            "{}",
+            res
+        );
+    }
+    {
+        // renaming a field to its own name doesn't generate a duplicate impl
+        let res = braced_derive("", "#[field_type(rename = \"r#x\")]", "").unwrap();
+        assert_eq!(res.matches("__multiconst_field_x").count(), 1, "{}", res);
+    }
+    {
+        let res = braced_derive(
+            "",
+            "",
+            "#[field_type(alias = \"r#type\")] #[field_type(alias = \"3\")]",
+        )
+        .unwrap();
+        assert!(res.consecutive_unspace(&["'y'", "Type = u64"]), "{}", res);
+        assert!(
+            res.consecutive_unspace(&["'t'", "'y'", "'p'", "'e'", "Type = u64"]),
+            "{}",
+            res
+        );
+        assert!(
+            res.consecutive_unspace(&["Usize < 3 >", "Type = u64"]),
+            "{}",
+            res
+        );
+        assert!(
+            res.consecutive_unspace(&["FIELD_NAMES", "[\"x\", \"y\",]"]),
+            "{}",
+            res
+        );
+    }
+    {
+        let res = parse_derive("enum Foo { Bar { #[field_type(rename = \"qux\")] r#type: u8 } }")
+            .unwrap();
+        assert!(
+            res.consecutive_unspace(&["VariantField", "'B'", "'q'", "'u'", "'x'", "Type = u8"]),
+            "{}",
+            res
+        );
+    }
+    for attr in &[
+        "rename = \"a b\"",
+        "alias = \"\"",
+        "alias = \"1.0\"",
+        "rename",
+    ] {
+        let s = format!("struct Foo {{ #[field_type({})] x: u8 }}", attr);
+        parse_derive(&s).unwrap_err();
+    }
+    for input in &[
+        "#[field_type(alias = \"a\")] struct Foo;",
+        "#[field_type(rename = \"a\")] enum Foo { Bar }",
+        "enum Foo { #[field_type(alias = \"a\")] Bar }",
+    ] {
+        let err = parse_derive(input).unwrap_err();
+        assert!(err.contains("expected"), "{}", err);
+        assert!(!err.contains("alias"), "{}", err);
+        assert!(!err.contains("rename"), "{}", err);
+    }
+}
+
+#[test]
+fn duplicate_field_names() {
+    for input in &[
+        "struct Foo { #[field_type(rename = \"c\")] a: u8, c: u8 }",
+        "struct Foo { #[field_type(alias = \"a\")] a: u8 }",
+        "struct Foo { #[field_type(alias = \"b\")] a: u8, #[field_type(alias = \"b\")] c: u8 }",
+        "struct Foo { #[field_type(flatten(0))] a: (u8,), #[field_type(rename = \"0\")] c: u8 }",
+        "struct Foo(u8, #[field_type(rename = \"0\")] u8);",
+        "enum Foo { Bar { #[field_type(alias = \"b\")] a: u8, b: u8 } }",
+    ] {
+        let err = parse_derive(input).unwrap_err();
+        assert!(err.contains("field name is used twice"), "{}", err);
+        assert!(err.contains("field name is first used here"), "{}", err);
+    }
+
+    // names only need to be unique within each struct or variant,
+    // and private fields don't have a `FieldType` impl
+    for input in &[
+        "enum Foo { Bar { #[field_type(alias = \"b\")] a: u8 }, Baz { b: u8 } }",
+        "struct Foo { #[field_type(priv)] a: u8, #[field_type(alias = \"a\")] c: u8 }",
+        "struct Foo { #[field_type(rename = \"c\")] a: u8, #[field_type(rename = \"a\")] c: u8 }",
+    ] {
+        parse_derive(input).unwrap();
+    }
+}
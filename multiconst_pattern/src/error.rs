@@ -16,7 +16,7 @@ use crate::{
 };
 
 #[derive(Debug)]
-pub(crate) struct Error {
+pub struct Error {
     messages: Vec<CompileError>,
 }
 
@@ -45,7 +45,6 @@ impl Error {
         )
     }
 
-    #[allow(dead_code)]
     pub(crate) fn join(mut self, mut other: Error) -> Error {
         self.messages.append(&mut other.messages);
         self
@@ -86,3 +85,42 @@ impl Error {
             .collect()
     }
 }
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Collects errors, to report multiple errors at once.
+pub(crate) struct ErrorAccumulator {
+    error: Option<Error>,
+}
+
+impl ErrorAccumulator {
+    pub(crate) fn new() -> Self {
+        Self { error: None }
+    }
+
+    pub(crate) fn push(&mut self, error: Error) {
+        self.error = Some(match self.error.take() {
+            Some(prev) => prev.join(error),
+            None => error,
+        });
+    }
+
+    /// Collects the error of `res`, if there is one.
+    pub(crate) fn handle<T>(&mut self, res: Result<T, Error>) -> Option<T> {
+        match res {
+            Ok(x) => Some(x),
+            Err(e) => {
+                self.push(e);
+                None
+            }
+        }
+    }
+
+    /// Returns all the collected errors joined together, if there's any.
+    pub(crate) fn into_result(self) -> Result<(), Error> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
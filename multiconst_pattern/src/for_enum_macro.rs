@@ -0,0 +1,325 @@
+use used_proc_macro::{
+    Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+};
+
+use alloc::{format, string::ToString, vec::Vec};
+
+use crate::{
+    parsing::{ParseBuffer, ParseStream},
+    syntax::{tokenize_delim, Attributes, Crate, Spans},
+    type_::{ArrayType, ParsedType, Type},
+    utils::{TokenStreamExt, TokenTreeExt, WithSpan},
+    Error,
+};
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) fn macro_impl(ts: TokenStream) -> Result<TokenStream, TokenStream> {
+    let input = &mut ParseBuffer::new(ts);
+    let crate_kw = Crate::parse(input).unwrap();
+
+    (|| -> Result<TokenStream, Error> {
+        let mut out = TokenStream::new();
+
+        while !input.is_empty() {
+            parse_one_enum(&crate_kw, input, &mut out)?;
+        }
+
+        Ok(out)
+    })()
+    .map_err(|e| e.to_compile_error(&crate_kw))
+}
+
+struct Variant {
+    attrs: Attributes,
+    ident: Ident,
+}
+
+fn parse_one_enum(
+    crate_kw: &Crate,
+    input: ParseStream<'_>,
+    ts: &mut TokenStream,
+) -> Result<(), Error> {
+    let attrs = Attributes::parse(input);
+    let vis = input.parse_vis();
+    let enum_token = input.parse_keyword("enum")?;
+    let name = input.parse_ident()?;
+
+    let variants = match input.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            parse_variants(&group)?
+        }
+        Some(tt) => {
+            return Err(Error::with_span(
+                tt.span(),
+                "expected `{}`-delimited enum variants",
+            ))
+        }
+        None => {
+            return Err(input.error("expected `{}`-delimited enum variants after this"));
+        }
+    };
+
+    input.parse_punct(':')?;
+    let type_span = input.span();
+
+    let (brackets, elem_ty, len) = match ParsedType::parse(input)? {
+        Type::Array(ArrayType {
+            brackets,
+            elem_ty,
+            len,
+        }) => (brackets, elem_ty.definite_length_real_type()?, len),
+        _ => {
+            return Err(Error::with_span(
+                type_span,
+                "expected an array type for the discriminants",
+            ))
+        }
+    };
+
+    let equals = input.parse_punct('=')?;
+    let expr = input.tokens_until(|tt| tt.is_punct(';'));
+    if expr.is_empty() {
+        return Err(Error::with_span(
+            equals.span(),
+            "expected expression after this",
+        ));
+    }
+    input.parse_punct(';')?;
+
+    let enum_span = enum_token.span();
+    let variant_count = variants.len();
+    let count_lit = || {
+        TokenStream::from(TokenTree::Literal(
+            Literal::usize_unsuffixed(variant_count).with_span(brackets),
+        ))
+    };
+
+    // the length of the array must be the amount of variants,
+    // non-literal lengths are checked by the compiler.
+    let mut len_assertion = None;
+    let len = match len {
+        Some(len) => {
+            let mut iter = len.clone().into_iter();
+            let written = match (iter.next(), iter.next()) {
+                (Some(TokenTree::Literal(lit)), None) => lit
+                    .to_string()
+                    .parse::<usize>()
+                    .ok()
+                    .map(|x| (x, lit.span())),
+                _ => None,
+            };
+
+            match written {
+                Some((written, span)) if written != variant_count => {
+                    let msg = format!(
+                        "the array length is {}, but the enum has {} variants",
+                        written, variant_count,
+                    );
+                    return Err(Error::with_span(span, msg));
+                }
+                Some(_) => {}
+                None => len_assertion = Some(len.clone()),
+            }
+            len
+        }
+        None => count_lit(),
+    };
+
+    let priv_const_name = Ident::new(
+        &crate::pattern_processing::hidden_const_name("ENUM", &name),
+        crate_kw.ident.span().located_at(enum_span),
+    );
+
+    let elem_ty = elem_ty.to_tokens();
+
+    // the array at the discriminant's position
+    let discriminant = |i: usize, span: Span, ts: &mut TokenStream| {
+        ts.append_one(priv_const_name.clone());
+        let index = TokenTree::Literal(Literal::usize_unsuffixed(i).with_span(span));
+        ts.append_one(Group::new(Delimiter::Bracket, index.into()).with_span(span));
+    };
+
+    ///////////////////
+
+    ts.append_keyword("const", enum_span);
+    ts.append_one(priv_const_name.clone());
+    ts.append_one(Punct::new(':', Spacing::Alone).with_span(enum_span));
+    tokenize_delim(Delimiter::Bracket, brackets, ts, |ts| {
+        ts.extend(elem_ty.clone());
+        ts.append_one(Punct::new(';', Spacing::Alone).with_span(brackets));
+        ts.extend(len);
+    });
+    ts.append_one(Punct::new('=', Spacing::Alone).with_span(enum_span));
+    ts.extend(expr);
+    ts.append_one(Punct::new(';', Spacing::Alone).with_span(enum_span));
+
+    // `const _: [(); variant_count] = [(); len];`
+    if let Some(len) = len_assertion {
+        let unit_array = |len: TokenStream, ts: &mut TokenStream| {
+            tokenize_delim(Delimiter::Bracket, brackets, ts, |ts| {
+                ts.append_one(Group::new(Delimiter::Parenthesis, TokenStream::new()));
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(brackets));
+                ts.extend(len);
+            });
+        };
+        ts.append_keyword("const", brackets);
+        ts.append_one(Ident::new("_", brackets));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(brackets));
+        unit_array(count_lit(), ts);
+        ts.append_one(Punct::new('=', Spacing::Alone).with_span(brackets));
+        unit_array(len, ts);
+        ts.append_one(Punct::new(';', Spacing::Alone).with_span(brackets));
+    }
+
+    ///////////////////
+
+    ts.extend(attrs.attrs);
+    ts.extend(vis.clone());
+    ts.append_one(enum_token);
+    ts.append_one(name.clone());
+    tokenize_delim(Delimiter::Brace, enum_span, ts, |ts| {
+        for (i, Variant { attrs, ident }) in variants.iter().enumerate() {
+            let span = ident.span();
+            ts.extend(attrs.attrs.clone());
+            ts.append_one(ident.clone());
+            ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+            discriminant(i, span, ts);
+            ts.append_one(Punct::new(',', Spacing::Alone).with_span(span));
+        }
+    });
+
+    ///////////////////
+
+    let self_variant = |ident: &Ident, ts: &mut TokenStream| {
+        let span = ident.span();
+        ts.append_keyword("Self", span);
+        ts.append_one(Punct::new(':', Spacing::Joint).with_span(span));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(span));
+        ts.append_one(ident.clone());
+    };
+
+    let option_variant = |variant: &str, ts: &mut TokenStream| {
+        crate_kw.item_to_ts("Option", Spans::from_one(enum_span), ts);
+        ts.append_one(Punct::new(':', Spacing::Joint).with_span(enum_span));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(enum_span));
+        ts.append_keyword(variant, enum_span);
+    };
+
+    ts.append_keyword("impl", enum_span);
+    ts.append_one(name);
+    tokenize_delim(Delimiter::Brace, enum_span, ts, |ts| {
+        ts.extend(
+            "
+            /// All the variants of this enum, in declaration order.
+            "
+            .parse::<TokenStream>()
+            .unwrap(),
+        );
+        ts.extend(vis.clone());
+        ts.append_keyword("const", enum_span);
+        ts.append_keyword("ALL", enum_span);
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(enum_span));
+        tokenize_delim(Delimiter::Bracket, enum_span, ts, |ts| {
+            ts.append_keyword("Self", enum_span);
+            ts.append_one(Punct::new(';', Spacing::Alone).with_span(enum_span));
+            ts.append_one(Literal::usize_unsuffixed(variant_count).with_span(enum_span));
+        });
+        ts.append_one(Punct::new('=', Spacing::Alone).with_span(enum_span));
+        tokenize_delim(Delimiter::Bracket, enum_span, ts, |ts| {
+            for Variant { ident, .. } in &variants {
+                self_variant(ident, ts);
+                ts.append_one(Punct::new(',', Spacing::Alone).with_span(ident.span()));
+            }
+        });
+        ts.append_one(Punct::new(';', Spacing::Alone).with_span(enum_span));
+
+        ts.extend(
+            "
+            /// Gets the variant whose discriminant is `repr`,
+            /// returning `None` if no variant has that discriminant.
+            "
+            .parse::<TokenStream>()
+            .unwrap(),
+        );
+        ts.extend(vis);
+        ts.append_keyword("const", enum_span);
+        ts.append_keyword("fn", enum_span);
+        ts.append_keyword("from_repr", enum_span);
+        tokenize_delim(Delimiter::Parenthesis, enum_span, ts, |ts| {
+            ts.append_keyword("repr", enum_span);
+            ts.append_one(Punct::new(':', Spacing::Alone).with_span(enum_span));
+            ts.extend(elem_ty);
+        });
+        ts.append_one(Punct::new('-', Spacing::Joint).with_span(enum_span));
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(enum_span));
+        crate_kw.item_to_ts("Option", Spans::from_one(enum_span), ts);
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(enum_span));
+        ts.append_keyword("Self", enum_span);
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(enum_span));
+        tokenize_delim(Delimiter::Brace, enum_span, ts, |ts| {
+            for (i, Variant { ident, .. }) in variants.iter().enumerate() {
+                let span = ident.span();
+                ts.append_keyword("if", span);
+                ts.append_keyword("repr", span);
+                ts.append_one(Punct::new('=', Spacing::Joint).with_span(span));
+                ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+                discriminant(i, span, ts);
+                tokenize_delim(Delimiter::Brace, span, ts, |ts| {
+                    ts.append_keyword("return", span);
+                    option_variant("Some", ts);
+                    tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+                        self_variant(ident, ts);
+                    });
+                });
+            }
+            option_variant("None", ts);
+        });
+    });
+
+    Ok(())
+}
+
+fn parse_variants(group: &Group) -> Result<Vec<Variant>, Error> {
+    let input = &mut ParseBuffer::with_span(group.stream(), group.span());
+    let mut variants = Vec::new();
+
+    while !input.is_empty() {
+        let attrs = Attributes::parse(input);
+        let ident = input.parse_ident()?;
+
+        match input.peek() {
+            Some(TokenTree::Group(group)) => {
+                return Err(Error::with_span(
+                    group.span(),
+                    "only fieldless variants are supported",
+                ))
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                return Err(Error::with_span(
+                    p.span(),
+                    "the discriminants are taken from the array, \
+                     explicit discriminants aren't supported",
+                ))
+            }
+            _ => {}
+        }
+
+        variants.push(Variant { attrs, ident });
+
+        // the comma is only optional after the last variant
+        if !input.is_empty() {
+            input.parse_punct(',')?;
+        }
+    }
+
+    if variants.is_empty() {
+        return Err(Error::with_span(
+            group.span(),
+            "expected at least one variant",
+        ));
+    }
+
+    Ok(variants)
+}
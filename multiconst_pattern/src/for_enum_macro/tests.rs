@@ -0,0 +1,99 @@
+use crate::test_utils::StrExt;
+
+use alloc::string::{String, ToString};
+
+fn process_str(s: &str) -> Result<String, String> {
+    let s = alloc::format!("crate {}", s);
+    let ts = s.parse::<used_proc_macro::TokenStream>().unwrap();
+
+    crate::for_enum_macro::macro_impl(ts)
+        .map(|x| x.to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn inferred_length() {
+    let out = process_str("enum Op { A, B, C }: [u8; _] = expr;").unwrap();
+    assert!(
+        out.consecutive_unspace(&[
+            "const __PRIV_MULTICONST_ENUM__Op: [u8; 3] = expr;",
+            "enum Op { A = __PRIV_MULTICONST_ENUM__Op[0], B = __PRIV_MULTICONST_ENUM__Op[1],",
+        ]),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn variant_errors() {
+    {
+        let out = process_str("enum Op { A(u8) }: [u8; _] = expr;").unwrap_err();
+        assert!(out.consecutive_in_self(&["fieldless variants"]), "{}", out);
+    }
+    {
+        let out = process_str("enum Op { A = 3 }: [u8; _] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["explicit discriminants"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("enum Op { A B }: [u8; _] = expr;").unwrap_err();
+        assert!(out.consecutive_in_self(&["expected a `,`"]), "{}", out);
+    }
+    {
+        let out = process_str("enum Op {}: [u8; _] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["at least one variant"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn non_array_type() {
+    let out = process_str("enum Op { A }: (u8,) = expr;").unwrap_err();
+    assert!(
+        out.consecutive_in_self(&["expected an array type"]),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn explicit_length() {
+    {
+        let out = process_str("enum Op { A, B }: [u8; 2] = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["const __PRIV_MULTICONST_ENUM__Op: [u8; 2] = expr;"]),
+            "{}",
+            out
+        );
+        assert!(!out.contains("[()"), "{}", out);
+    }
+    for len in &["1", "3"] {
+        let out =
+            process_str(&alloc::format!("enum Op {{ A, B }}: [u8; {}] = expr;", len)).unwrap_err();
+        assert!(
+            out.consecutive_in_self(&[&alloc::format!(
+                "the array length is {}, but the enum has 2 variants",
+                len
+            )]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("enum Op { A, B }: [u8; LEN] = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const __PRIV_MULTICONST_ENUM__Op: [u8; LEN] = expr;",
+                "const _: [(); 2] = [(); LEN];",
+            ]),
+            "{}",
+            out
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use used_proc_macro::{Punct, Spacing, TokenStream, TokenTree};
 
 use crate::{
-    syntax::{Attributes, Crate, FieldName},
+    syntax::{Attributes, Crate, FieldName, Spans},
     utils::{TokenStreamExt, WithSpan},
     Error,
 };
@@ -20,6 +20,21 @@ pub(crate) fn field_macro_impl(input: TokenStream) -> Result<TokenStream, TokenS
     .map_err(|e| e.to_compile_error(&crate_kw))
 }
 
+pub(crate) fn field_path_macro_impl(input: TokenStream) -> Result<TokenStream, TokenStream> {
+    let input = &mut crate::parsing::ParseBuffer::new(input);
+    let crate_kw = Crate::parse(input).unwrap();
+    (|| -> Result<TokenStream, Error> {
+        let mut out = TokenStream::new();
+        let span = input.span();
+        let path = FieldName::parse_path(input)?;
+        input.assert_empty()?;
+        FieldName::path_to_token_stream(&path, &crate_kw, None, Spans::from_one(span), &mut out);
+
+        Ok(out)
+    })()
+    .map_err(|e| e.to_compile_error(&crate_kw))
+}
+
 pub(crate) fn field_name_aliases_macro_impl(
     input: TokenStream,
 ) -> Result<TokenStream, TokenStream> {
@@ -55,7 +70,14 @@ pub(crate) fn field_name_aliases_macro_impl(
                     return Err(Error::with_span(eq_span, "expected field name after this"));
                 }
 
-                FieldName::parse(input)?.to_token_stream(&crate_kw, &mut out);
+                let path_span = input.span();
+                let mut path = FieldName::parse_path(input)?;
+                if path.len() == 1 {
+                    path.remove(0).to_token_stream(&crate_kw, &mut out);
+                } else {
+                    let spans = Spans::from_one(path_span);
+                    FieldName::path_to_token_stream(&path, &crate_kw, None, spans, &mut out);
+                }
             } else {
                 let field_ident = FieldName::from_ident(&ident);
 
@@ -0,0 +1,1153 @@
+use used_proc_macro::{
+    Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+};
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    error::ErrorAccumulator,
+    parsing::{ParseBuffer, ParseStream},
+    pattern::{BindingAndType, Coercion, FieldPat, Pattern, StructPat},
+    pattern_processing::{self, CheckedLocal, ExtractConstCtx, FieldType, WholeFieldPat},
+    syntax::{self, tokenize_delim, tokenize_iter_delim, Attributes, Crate, FieldMarkers, Spans},
+    type_::{RealType, Type},
+    utils::{IsIdent, TokenStreamExt, TokenTreeExt, WithSpan},
+    Error,
+};
+
+use self::attribute_parsing::parse_multiconst_attrs;
+
+mod attribute_parsing;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Copy, Clone)]
+pub(crate) enum Usedwhere {
+    OutsideImpls,
+    InherentImpl,
+    /// In the impl of an extension trait, declaring the constants in the trait
+    TraitImpl,
+}
+
+/// Where the constants are output to.
+pub(crate) struct ConstOutput {
+    /// The constant items, and any modules they're in
+    pub(crate) items: TokenStream,
+    /// The declarations of the constants in an extension trait,
+    /// `None` if they're not declared in a trait.
+    pub(crate) trait_decls: Option<TokenStream>,
+}
+
+impl ConstOutput {
+    pub(crate) fn new(used_where: Usedwhere) -> Self {
+        Self {
+            items: TokenStream::new(),
+            trait_decls: match used_where {
+                Usedwhere::TraitImpl => Some(TokenStream::new()),
+                Usedwhere::OutsideImpls | Usedwhere::InherentImpl => None,
+            },
+        }
+    }
+
+    /// Outputs a `const` item, and its declaration in the trait when in an extension trait.
+    fn add_const(
+        &mut self,
+        attrs: TokenStream,
+        vis: &TokenStream,
+        name: Ident,
+        type_: TokenStream,
+        value: TokenStream,
+        is_hidden: bool,
+    ) {
+        let span = name.span();
+        let ts = &mut self.items;
+
+        match &mut self.trait_decls {
+            Some(decls) => {
+                decls.extend(attrs.clone());
+                if is_hidden {
+                    tokenize_doc_hidden(span, decls);
+                }
+                decls.append_keyword("const", span);
+                decls.append_one(name.clone());
+                decls.append_one(Punct::new(':', Spacing::Alone).with_span(span));
+                decls.extend(type_.clone());
+                decls.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+
+                // the documentation and deprecation are in the trait
+                ts.extend(syntax::remove_doc_attrs(attrs));
+            }
+            None => {
+                ts.extend(attrs);
+                ts.extend(vis.clone());
+            }
+        }
+
+        ts.append_keyword("const", span);
+        ts.append_one(name);
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(span));
+        ts.extend(type_);
+        ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+        ts.extend(value);
+        ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+    }
+}
+
+pub(crate) fn macro_impl(
+    ts: TokenStream,
+    used_where: Usedwhere,
+) -> Result<TokenStream, TokenStream> {
+    // #[cfg(feature = "__dbg")]
+    // std::println!("\n\n{:#?}\n\n", ts);
+
+    let input = &mut ParseBuffer::new(ts);
+    let crate_kw = Crate::parse(input).unwrap();
+
+    let mut out = ConstOutput::new(used_where);
+
+    parse_all_constants(
+        &crate_kw,
+        used_where,
+        &const_path(used_where),
+        input,
+        &mut out,
+    )
+    .map_err(|e| Error::to_compile_error(&e, &crate_kw))?;
+
+    // #[cfg(feature = "__dbg")]
+    // std::println!("\n\n{}\n\n", out.items);
+
+    Ok(out.items)
+}
+
+/// Implements the `extension_multiconst` macro,
+/// which declares an extension trait with the constants, and implements it.
+pub(crate) fn extension_macro_impl(ts: TokenStream) -> Result<TokenStream, TokenStream> {
+    let input = &mut ParseBuffer::new(ts);
+    let crate_kw = Crate::parse(input).unwrap();
+
+    parse_extension_trait(&crate_kw, input).map_err(|e| Error::to_compile_error(&e, &crate_kw))
+}
+
+fn parse_extension_trait(crate_kw: &Crate, input: ParseStream<'_>) -> Result<TokenStream, Error> {
+    let attrs = Attributes::parse(input);
+    let vis = input.parse_vis();
+    let trait_token = input.parse_keyword("trait")?;
+    let trait_name = input.parse_ident()?;
+    let for_token = input.parse_keyword("for")?;
+
+    let self_type = input.tokens_until(|tt| tt.is_group(Delimiter::Brace));
+    if self_type.is_empty() {
+        return Err(Error::with_span(
+            for_token.span(),
+            "expected the type that the trait is implemented for after this",
+        ));
+    }
+
+    let body = match input.next() {
+        Some(TokenTree::Group(group)) => group,
+        _ => return Err(input.error("expected `{}`-delimited constants after this")),
+    };
+    input.assert_empty()?;
+
+    let used_where = Usedwhere::TraitImpl;
+    let mut out = ConstOutput::new(used_where);
+    parse_all_constants(
+        crate_kw,
+        used_where,
+        &const_path(used_where),
+        &mut ParseBuffer::with_span(body.stream(), body.span()),
+        &mut out,
+    )?;
+
+    let span = trait_token.span();
+    let mut ts = TokenStream::new();
+
+    ts.extend(attrs.attrs);
+    ts.extend(vis);
+    ts.append_one(trait_token);
+    ts.append_one(trait_name.clone());
+    ts.append_one(
+        Group::new(Delimiter::Brace, out.trait_decls.unwrap_or_default()).with_span(body.span()),
+    );
+
+    ts.append_keyword("impl", span);
+    ts.append_one(trait_name);
+    ts.append_one(for_token);
+    ts.extend(self_type);
+    ts.append_one(Group::new(Delimiter::Brace, out.items).with_span(body.span()));
+
+    Ok(ts)
+}
+
+// the path that the constants are accessed through
+pub(crate) fn const_path(used_where: Usedwhere) -> TokenStream {
+    match used_where {
+        Usedwhere::OutsideImpls => TokenStream::new(),
+        Usedwhere::InherentImpl | Usedwhere::TraitImpl => TokenStream::from_array([
+            TokenTree::Ident(Ident::new("Self", Span::mixed_site())),
+            Punct::new(':', Spacing::Joint).into(),
+            Punct::new(':', Spacing::Alone).into(),
+        ]),
+    }
+}
+
+pub(crate) fn parse_all_constants(
+    crate_kw: &Crate,
+    used_where: Usedwhere,
+    const_path: &TokenStream,
+    input: ParseStream<'_>,
+    out: &mut ConstOutput,
+) -> Result<(), Error> {
+    let mut errors = ErrorAccumulator::new();
+    // the names of the constants declared by the previous items, without cfgs
+    let mut declared: Vec<Ident> = Vec::new();
+
+    while !input.is_empty() {
+        let outer_attrs = Attributes::parse(input);
+        let vis = input.parse_vis();
+
+        // Each item is parsed from its own tokens,
+        // so that the items after it are parsed even if it has errors.
+        let is_module = matches!(input.peek(), Some(tt) if tt.is_ident("mod"));
+        let span = input.last_span();
+        let mut item_tokens = input
+            .tokens_until(|tt| tt.is_punct(';') || (is_module && tt.is_group(Delimiter::Brace)));
+        item_tokens.extend(input.next());
+        let item = &mut ParseBuffer::with_span(item_tokens, span);
+
+        let res = if let Some(mod_token) = item.peek_parse_keyword("mod") {
+            parse_module(crate_kw, used_where, outer_attrs, vis, mod_token, item, out)
+        } else if let (Usedwhere::TraitImpl, Some(tt)) =
+            (used_where, vis.clone().into_iter().next())
+        {
+            Err(Error::with_span(
+                tt.span(),
+                "constants in traits can't have visibility qualifiers",
+            ))
+        } else {
+            parse_one_constant(
+                crate_kw,
+                used_where,
+                const_path,
+                outer_attrs,
+                vis,
+                item,
+                &mut declared,
+                out,
+            )
+        };
+
+        errors.handle(res.and_then(|()| item.assert_empty()));
+    }
+
+    // #[cfg(feature = "__dbg")]
+    // ::std::println!("{}", out.items);
+
+    errors.into_result()
+}
+
+// parses a `mod name { ... }` containing constants
+fn parse_module(
+    crate_kw: &Crate,
+    used_where: Usedwhere,
+    attrs: Attributes,
+    vis: TokenStream,
+    mod_token: Ident,
+    input: ParseStream<'_>,
+    out: &mut ConstOutput,
+) -> Result<(), Error> {
+    let span = mod_token.span();
+
+    match used_where {
+        Usedwhere::OutsideImpls => {}
+        Usedwhere::InherentImpl => {
+            return Err(Error::with_span(
+                span,
+                "modules can't be declared inside impl blocks",
+            ))
+        }
+        Usedwhere::TraitImpl => {
+            return Err(Error::with_span(
+                span,
+                "modules can't be declared inside traits",
+            ))
+        }
+    }
+
+    let name = input.parse_ident()?;
+    let group = match input.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => {
+            return Err(Error::with_span(
+                tt.span(),
+                "expected `{}`-delimited module contents",
+            ))
+        }
+        None => return Err(input.error("expected `{}`-delimited module contents after this")),
+    };
+
+    let mut contents = ConstOutput::new(used_where);
+    parse_all_constants(
+        crate_kw,
+        used_where,
+        &TokenStream::new(),
+        &mut ParseBuffer::with_span(group.stream(), group.span()),
+        &mut contents,
+    )?;
+
+    let ts = &mut out.items;
+    ts.extend(attrs.attrs);
+    ts.extend(vis);
+    ts.append_one(mod_token);
+    ts.append_one(name);
+    tokenize_delim(Delimiter::Brace, group.span(), ts, |ts| {
+        ts.extend(
+            "
+            #[allow(unused_imports)]
+            use super::*;
+            "
+            .parse::<TokenStream>()
+            .unwrap()
+            .with_span(span),
+        );
+        ts.extend(contents.items);
+    });
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn parse_one_constant(
+    crate_kw: &Crate,
+    used_where: Usedwhere,
+    const_path: &TokenStream,
+    mut outer_attrs: Attributes,
+    vis: TokenStream,
+    input: ParseStream<'_>,
+    declared: &mut Vec<Ident>,
+    out: &mut ConstOutput,
+) -> Result<(), Error> {
+    let multiconst_attrs = parse_multiconst_attrs(&mut outer_attrs)?;
+
+    // with the `debug` argument, the code generated for this constant is
+    // output separately first, so that it can be shown in an error.
+    let prev_out = multiconst_attrs.debug.as_ref().map(|_| ConstOutput {
+        items: core::mem::take(&mut out.items),
+        trait_decls: out.trait_decls.as_mut().map(core::mem::take),
+    });
+
+    let const_token = input.parse_keyword("const")?;
+    let pattern = Pattern::parse(input)?;
+    let _colon = input.parse_punct(':')?;
+    let type_ = Type::parse(input)?;
+    let type_ = crate::pattern_processing::real_type_from(&pattern, type_)?;
+
+    let const_span = const_token.span();
+    let equals = input.parse_punct('=')?;
+
+    let expr = input.tokens_until(|tt| tt.is_punct(';'));
+    if expr.is_empty() {
+        return Err(Error::with_span(
+            equals.span(),
+            "expected expression after this",
+        ));
+    }
+    input.parse_punct(';')?;
+
+    let expr_str = if multiconst_attrs.provenance_docs {
+        crate::utils::tokens_to_compact_string(expr.clone())
+    } else {
+        String::new()
+    };
+
+    // the hidden constants are named after the first constant without cfgs,
+    // since constants with cfgs can have the same name as other constants.
+    let first_const =
+        pattern_processing::find_first_const_ident(&pattern, &|b| !b.attrs.is_conditional())
+            .or_else(|| pattern_processing::find_first_const_ident(&pattern, &|_| true));
+    let hidden_name = |kind: &str, span: Span| match first_const {
+        Some(ident) => Ident::new(
+            &pattern_processing::hidden_const_name(kind, ident),
+            crate_kw.ident.span().located_at(span),
+        ),
+        // there are no constants to name the hidden constants after
+        None if kind.is_empty() => Ident::new("_", span),
+        None => Ident::new(
+            &alloc::format!("{}{}__", pattern_processing::HIDDEN_CONST_PREFIX, kind),
+            span,
+        ),
+    };
+
+    let mut bats: Vec<BindingAndType> = Vec::new();
+    let mut tuple_rem_lens: Vec<TokenStream> = Vec::new();
+    let mut elem_assertions: Vec<TokenStream> = Vec::new();
+    let mut checked_locals: Vec<CheckedLocal> = Vec::new();
+    let tuple_rem_pat_const = hidden_name("LENS", const_span);
+
+    // the module with the marker types can't be declared inside impls,
+    // and is named after the constants to not conflict with other modules.
+    let mut field_markers = match (used_where, first_const) {
+        (Usedwhere::OutsideImpls, Some(_)) => {
+            Some(FieldMarkers::new(hidden_name("FIELDS", const_span)))
+        }
+        _ => None,
+    };
+
+    let mut pctx = ExtractConstCtx {
+        bats: &mut bats,
+        tuple_rem_lens: &mut tuple_rem_lens,
+        tuple_rem_pat_const: &tuple_rem_pat_const,
+        elem_assertions: &mut elem_assertions,
+        checked_locals: &mut checked_locals,
+        crate_kw,
+        field_markers: field_markers.as_mut(),
+        field_path: String::new(),
+        element_index: None,
+        in_slice: false,
+        is_refutable: false,
+    };
+    crate::pattern_processing::extract_const_names_tys(
+        &pattern,
+        FieldType::Direct(&type_),
+        WholeFieldPat::No,
+        &mut pctx,
+    )?;
+    let is_refutable = pctx.is_refutable;
+
+    if let (Some(registry), true) = (&multiconst_attrs.registry, bats.is_empty()) {
+        return Err(Error::with_span(
+            registry.span(),
+            "the registry requires the pattern to declare at least one constant",
+        ));
+    }
+
+    if let Some(registry) = &multiconst_attrs.registry {
+        pattern_processing::check_reserved_name(registry)?;
+        pattern_processing::check_not_declared(registry, bats.iter().map(|b| &b.constant))?;
+    }
+
+    // constants with cfgs can have the same name as constants in other `const` items,
+    // so long as they're not enabled at the same time.
+    if !outer_attrs.is_conditional() {
+        let names: Vec<&Ident> = bats
+            .iter()
+            .filter(|b| !b.attrs.is_conditional())
+            .map(|b| &b.constant)
+            .chain(&multiconst_attrs.registry)
+            .collect();
+
+        let mut errors = ErrorAccumulator::new();
+        for name in &names {
+            errors.handle(pattern_processing::check_not_declared(name, &*declared));
+        }
+        errors.into_result()?;
+
+        declared.extend(names.into_iter().cloned());
+    }
+
+    let outer_cfgs = outer_attrs.cfg_attrs();
+
+    let mut allow_unused = TokenStream::new();
+    tokenize_allow_unused_variables(const_span, &mut allow_unused);
+
+    // Constants with `#[cfg]` attributes (or `#[cfg_attr]`s that expand to `#[cfg]`)
+    // are destructured in their own hidden constant, which has those cfgs,
+    // so that the positions of the other constants don't depend on cfgs.
+    let binding_preds: Vec<Option<TokenStream>> =
+        bats.iter().map(|b| b.attrs.cfg_predicate()).collect();
+    let has_cfgs = binding_preds.iter().any(Option::is_some);
+
+    // the constants are only enabled if any of their cfgs are
+    let any_binding_cfg = |attrs: &mut TokenStream| {
+        if has_cfgs && binding_preds.iter().all(Option::is_some) {
+            let preds = binding_preds.iter().flatten().cloned().collect();
+            let pred = syntax::join_cfg_predicates("any", preds).unwrap();
+            tokenize_cfg_attr(pred, const_span, attrs);
+        }
+    };
+
+    if let Some(field_markers) = field_markers {
+        let mut attrs = outer_cfgs.clone();
+        any_binding_cfg(&mut attrs);
+        out.items
+            .extend(field_markers.into_module_tokens(crate_kw, attrs));
+    }
+
+    // the assertions are in their own constant so that they're evaluated
+    // even if the pattern doesn't compile, which it doesn't when they fail.
+    // Associated constants are only evaluated when used, so they aren't output in impls.
+    if matches!(used_where, Usedwhere::OutsideImpls) && !elem_assertions.is_empty() {
+        let mut attrs = outer_cfgs.clone();
+        any_binding_cfg(&mut attrs);
+
+        let mut value = TokenStream::new();
+        tokenize_delim(Delimiter::Brace, const_span, &mut value, |ts| {
+            for assertion in elem_assertions {
+                let span = first_token_span(&assertion).unwrap_or(const_span);
+                ts.append_keyword("let", span);
+                ts.append_one(
+                    Group::new(Delimiter::Parenthesis, TokenStream::new()).with_span(span),
+                );
+                ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+                ts.extend(assertion);
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+            }
+        });
+
+        let unit = Group::new(Delimiter::Parenthesis, TokenStream::new()).with_span(const_span);
+        let unit = TokenStream::from(TokenTree::from(unit));
+        let name = Ident::new("_", const_span);
+        out.add_const(attrs, &TokenStream::new(), name, unit, value, true);
+    }
+
+    // with multiple hidden constants, the expression is evaluated once,
+    // in a hidden constant that the others destructure.
+    let value_const_expr = if has_cfgs {
+        let name = hidden_name("VALUE", const_span);
+
+        // the expression doesn't need to compile when all the constants are cfg-ed out
+        let mut attrs = outer_cfgs.clone();
+        any_binding_cfg(&mut attrs);
+
+        out.add_const(
+            attrs,
+            &TokenStream::new(),
+            name.clone(),
+            type_.to_tokens(),
+            expr.clone(),
+            true,
+        );
+
+        let mut ts = const_path.clone();
+        ts.append_one(name);
+        Some(ts)
+    } else {
+        None
+    };
+
+    let hidden_const = HiddenConst {
+        crate_kw,
+        is_refutable,
+        pattern: &pattern,
+        type_: &type_,
+        expr: value_const_expr.as_ref().unwrap_or(&expr),
+        checked_locals: &checked_locals,
+        const_span,
+    };
+
+    {
+        let no_contants = bats.is_empty();
+
+        let priv_const_name = hidden_name("", const_span);
+
+        // hack to assert that vis is valid syntax when it's not otherwise used
+        if no_contants {
+            out.items.extend(vis.clone());
+        }
+
+        ///////////////////
+
+        if !tuple_rem_lens.is_empty() {
+            let mut type_ = TokenStream::new();
+            type_.append_one(Punct::new('&', Spacing::Alone).with_span(const_span));
+
+            let mut usize_ = TokenStream::new();
+            crate_kw.item_to_ts("usize", Spans::from_one(const_span), &mut usize_);
+            type_.append_one(Group::new(Delimiter::Bracket, usize_));
+
+            let mut value = TokenStream::new();
+            value.append_one(Punct::new('&', Spacing::Alone).with_span(const_span));
+
+            let mut lens_ts = TokenStream::new();
+            for len in tuple_rem_lens {
+                lens_ts.extend(len);
+                lens_ts.append_one(Punct::new(',', Spacing::Alone).with_span(const_span));
+            }
+            value.append_one(Group::new(Delimiter::Bracket, lens_ts));
+
+            out.add_const(
+                outer_cfgs.clone(),
+                &TokenStream::new(),
+                tuple_rem_pat_const,
+                type_,
+                value,
+                true,
+            );
+        }
+
+        ///////////////////
+
+        let uncfgd_bats: Vec<&BindingAndType> = bats
+            .iter()
+            .zip(&binding_preds)
+            .filter(|(_, pred)| pred.is_none())
+            .map(|(bat, _)| bat)
+            .collect();
+
+        let mut priv_type_span = priv_const_name.span();
+        if !uncfgd_bats.is_empty() || no_contants {
+            let mut attrs = outer_cfgs.clone();
+            if has_cfgs {
+                attrs.extend(allow_unused.clone());
+            }
+            priv_type_span = hidden_const.output(priv_const_name.clone(), attrs, &uncfgd_bats, out);
+        }
+
+        let mut uncfgd_index = 0;
+
+        ///////////////////
+
+        // the type of the first constant, used as the type of the registry's values
+        let mut first_type: Option<TokenStream> = None;
+        let mut registry_entries: Vec<(Ident, Option<TokenStream>)> = Vec::new();
+
+        for (bat, bat_pred) in bats.iter().zip(binding_preds) {
+            let nconst_span = bat.constant.span();
+
+            let mut public_type = TokenStream::new();
+            match &bat.coercion {
+                Some(coercion) => coercion.type_to_token_stream(&mut public_type),
+                None => public_type.extend(bat.type_.ty.clone()),
+            }
+
+            if multiconst_attrs.registry.is_some() {
+                first_type.get_or_insert_with(|| public_type.clone());
+                registry_entries.push((bat.constant.clone(), bat_pred.clone()));
+            }
+
+            let (value_const, value_index, type_span) = match bat_pred {
+                None => {
+                    uncfgd_index += 1;
+                    (priv_const_name.clone(), uncfgd_index - 1, priv_type_span)
+                }
+                Some(pred) => {
+                    let name = pattern_processing::hidden_const_name("CFG", &bat.constant);
+                    let name = Ident::new(&name, crate_kw.ident.span().located_at(nconst_span));
+
+                    let mut attrs = outer_cfgs.clone();
+                    tokenize_cfg_attr(pred, nconst_span, &mut attrs);
+                    attrs.extend(allow_unused.clone());
+                    let type_span = hidden_const.output(name.clone(), attrs, &[bat], out);
+
+                    (name, 0, type_span)
+                }
+            };
+
+            let mut attrs = bat.attrs.attrs.clone();
+            attrs.extend(outer_attrs.attrs.clone());
+            if !multiconst_attrs.doc_templates.is_empty() {
+                let name = crate::utils::ident_to_string_no_raw(&bat.constant);
+                for template in &multiconst_attrs.doc_templates {
+                    let index = bat.element_index.as_deref();
+                    let doc = template.expand(index, &bat.field_path, &name)?;
+                    tokenize_doc_attr(doc, nconst_span, &mut attrs);
+                }
+            }
+            if multiconst_attrs.provenance_docs {
+                let doc = provenance_doc(&bat.field_path, &expr_str);
+                tokenize_doc_attr(Literal::string(""), nconst_span, &mut attrs);
+                tokenize_doc_attr(Literal::string(&doc), nconst_span, &mut attrs);
+            }
+            // spanned like the type of the hidden constant,
+            // so that rustc deduplicates the errors for that type.
+            let mut field = TokenStream::new();
+            field.extend(const_path.clone());
+            let value_const_span = value_const.span().located_at(type_span);
+            field.append_one(value_const.with_span(value_const_span));
+            field.append_one(Punct::new('.', Spacing::Alone).with_span(type_span));
+            field.append_one(Literal::usize_unsuffixed(value_index).with_span(type_span));
+
+            let mut value = TokenStream::new();
+            let ts = &mut value;
+            match &bat.coercion {
+                Some(Coercion {
+                    ampersand, is_str, ..
+                }) => {
+                    let span = *ampersand;
+                    if *is_str {
+                        crate_kw.item_to_ts("bytes_to_str", Spans::from_one(span), ts);
+                        ts.append_one(Punct::new('!', Spacing::Alone).with_span(span));
+                    }
+                    tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+                        ts.append_one(Punct::new('&', Spacing::Alone).with_span(span));
+                        ts.extend(field);
+                    });
+                }
+                None => ts.extend(field),
+            }
+
+            out.add_const(attrs, &vis, bat.constant.clone(), public_type, value, false);
+        }
+
+        ///////////////////
+
+        if let (Some(registry), Some(first_type)) = (multiconst_attrs.registry, first_type) {
+            output_registry(
+                crate_kw,
+                const_path,
+                &vis,
+                &outer_cfgs,
+                registry,
+                first_type,
+                &registry_entries,
+                out,
+            );
+        }
+    }
+
+    if let (Some(debug), Some(prev_out)) = (&multiconst_attrs.debug, prev_out) {
+        output_debug(crate_kw, debug, prev_out, out);
+    }
+
+    Ok(())
+}
+
+// Outputs the code generated for a constant, along with an error that shows that code.
+//
+// `prev_out` is what was output before the constant, and `out` is what the constant output.
+fn output_debug(crate_kw: &Crate, debug: &Ident, mut prev_out: ConstOutput, out: &mut ConstOutput) {
+    let mut code = String::new();
+    if let Some(decls) = &out.trait_decls {
+        code.push_str("// in the trait\n");
+        code.push_str(&crate::utils::tokens_to_pretty_string(decls.clone()));
+        code.push_str("\n// in the impl\n");
+    }
+    code.push_str(&crate::utils::tokens_to_pretty_string(out.items.clone()));
+
+    let msg = format!(
+        "the code generated for this constant (shown because of the `debug` argument):\n\n{}",
+        code,
+    );
+
+    prev_out.items.extend(core::mem::take(&mut out.items));
+    prev_out
+        .items
+        .extend(Error::with_span(debug.span(), msg).to_compile_error(crate_kw));
+
+    if let (Some(prev_decls), Some(decls)) = (&mut prev_out.trait_decls, out.trait_decls.take()) {
+        prev_decls.extend(decls);
+    }
+
+    *out = prev_out;
+}
+
+fn first_token_span(ts: &TokenStream) -> Option<Span> {
+    ts.clone().into_iter().next().map(|tt| tt.span())
+}
+
+/// Outputs hidden constants that destructure the value,
+/// evaluating to a tuple of some of the bindings.
+struct HiddenConst<'a> {
+    crate_kw: &'a Crate,
+    is_refutable: bool,
+    pattern: &'a Pattern,
+    type_: &'a RealType,
+    expr: &'a TokenStream,
+    checked_locals: &'a [CheckedLocal],
+    const_span: Span,
+}
+
+impl HiddenConst<'_> {
+    // returns the span of the type of the constant
+    fn output(
+        &self,
+        name: Ident,
+        attrs: TokenStream,
+        bats: &[&BindingAndType],
+        out: &mut ConstOutput,
+    ) -> Span {
+        let const_span = self.const_span;
+
+        // the errors for the type of the constant are reported at the same span
+        // as those for the type of the only binding, so that rustc deduplicates them.
+        let type_span = match bats {
+            [bat] => first_token_span(&bat.type_.ty),
+            _ => None,
+        }
+        .unwrap_or_else(|| name.span());
+
+        let mut type_ = TokenStream::new();
+        tokenize_iter_delim(
+            Delimiter::Parenthesis,
+            type_span,
+            bats,
+            &mut type_,
+            |ts, bat| {
+                ts.extend(bat.type_.ty.clone());
+                syntax::tokenize_comma(const_span, ts);
+            },
+        );
+
+        let output_bindings = |ts: &mut TokenStream| {
+            for CheckedLocal {
+                binding,
+                type_: btype,
+                by_ref,
+            } in self.checked_locals
+            {
+                let bspan = binding.span();
+                ts.append_keyword("let", bspan);
+                ts.append_one(Ident::new("_", bspan));
+                ts.append_one(Punct::new(':', Spacing::Alone).with_span(bspan));
+                if *by_ref {
+                    ts.append_one(Punct::new('&', Spacing::Alone).with_span(bspan));
+                }
+                ts.extend(btype.ty.clone());
+                ts.append_one(Punct::new('=', Spacing::Alone).with_span(bspan));
+                ts.append_one(binding.clone());
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(bspan));
+            }
+
+            tokenize_iter_delim(Delimiter::Parenthesis, type_span, bats, ts, |ts, bat| {
+                if bat.by_ref {
+                    // errors if the element isn't `Copy`, instead of moving out of the reference
+                    let lspan = bat.local.span();
+                    self.crate_kw
+                        .item_to_ts("copy_slice_element", Spans::from_one(lspan), ts);
+                    ts.append_one(Punct::new('!', Spacing::Alone).with_span(lspan));
+                    ts.append_one(
+                        Group::new(
+                            Delimiter::Parenthesis,
+                            TokenTree::from(bat.local.clone()).into(),
+                        )
+                        .with_span(lspan),
+                    );
+                } else {
+                    ts.append_one(bat.local.clone());
+                }
+                syntax::tokenize_comma(const_span, ts);
+            });
+        };
+
+        let mut value = TokenStream::new();
+        tokenize_delim(Delimiter::Brace, type_span, &mut value, |ts| {
+            let mut accessed = Vec::new();
+            let mut pattern = TokenStream::new();
+            self.pattern.to_token_stream(&mut pattern, &mut accessed);
+
+            let mut finish = |ts: &mut TokenStream| {
+                self.output_destructured_after(&mut accessed, 0, ts, &output_bindings)
+            };
+
+            if self.is_refutable {
+                // the expression is stored in a variable to annotate its type
+                let value_var = Ident::new("__multiconst_value", Span::mixed_site());
+                ts.append_keyword("let", const_span);
+                ts.append_one(value_var.clone());
+                ts.append_one(Punct::new(':', Spacing::Alone).with_span(const_span));
+                ts.extend(self.type_.to_tokens());
+                ts.append_one(Punct::new('=', Spacing::Alone).with_span(const_span));
+                ts.extend(self.expr.clone());
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(const_span));
+
+                self.output_destructure(
+                    TokenTree::from(value_var).into(),
+                    pattern,
+                    ts,
+                    &mut finish,
+                );
+            } else {
+                ts.append_keyword("let", const_span);
+                ts.extend(pattern);
+                ts.append_one(Punct::new(':', Spacing::Alone).with_span(const_span));
+                ts.extend(self.type_.to_tokens());
+                ts.append_one(Punct::new('=', Spacing::Alone).with_span(const_span));
+                ts.extend(self.expr.clone());
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(const_span));
+
+                finish(ts);
+            }
+        });
+
+        out.add_const(attrs, &TokenStream::new(), name, type_, value, true);
+
+        type_span
+    }
+
+    // Destructures `value` with `pattern`, then outputs the code in `rest`.
+    //
+    // slice patterns are refutable, so they're destructured with a `match`.
+    fn output_destructure(
+        &self,
+        value: TokenStream,
+        pattern: TokenStream,
+        ts: &mut TokenStream,
+        rest: &mut dyn FnMut(&mut TokenStream),
+    ) {
+        let const_span = self.const_span;
+
+        if !self.is_refutable {
+            ts.append_keyword("let", const_span);
+            ts.extend(pattern);
+            ts.append_one(Punct::new('=', Spacing::Alone).with_span(const_span));
+            ts.extend(value);
+            ts.append_one(Punct::new(';', Spacing::Alone).with_span(const_span));
+            rest(ts);
+            return;
+        }
+
+        ts.append_keyword("match", const_span);
+        ts.extend(value);
+        tokenize_delim(Delimiter::Brace, const_span, ts, |ts| {
+            ts.extend(pattern);
+            ts.append_one(Punct::new('=', Spacing::Joint).with_span(const_span));
+            ts.append_one(Punct::new('>', Spacing::Alone).with_span(const_span));
+            tokenize_delim(Delimiter::Brace, const_span, ts, |ts| rest(ts));
+
+            // this arm is unreachable when the slice patterns are in other `match`es
+            ts.append_one(Punct::new('#', Spacing::Alone).with_span(const_span));
+            tokenize_delim(Delimiter::Bracket, const_span, ts, |ts| {
+                ts.append_keyword("allow", const_span);
+                tokenize_delim(Delimiter::Parenthesis, const_span, ts, |ts| {
+                    ts.append_keyword("unreachable_patterns", const_span);
+                });
+            });
+            ts.append_keyword("_", const_span);
+            ts.append_one(Punct::new('=', Spacing::Joint).with_span(const_span));
+            ts.append_one(Punct::new('>', Spacing::Alone).with_span(const_span));
+            let spans = Spans::from_one(const_span);
+            self.crate_kw.item_to_ts("slice_length_mismatch", spans, ts);
+            ts.append_one(Punct::new('!', Spacing::Alone).with_span(const_span));
+            ts.append_one(
+                Group::new(Delimiter::Parenthesis, TokenStream::new()).with_span(const_span),
+            );
+            syntax::tokenize_comma(const_span, ts);
+        });
+    }
+
+    // Destructures the local variables that `Pattern::to_token_stream` outputs
+    // for union patterns and struct patterns with fields named by string literals,
+    // starting from the `i`th one, then outputs the code in `finish`.
+    //
+    // Union fields are read in an `unsafe` block that only contains the read,
+    // and fields named by string literals are read with
+    // the methods that the `FieldType` derive generates.
+    fn output_destructured_after<'a>(
+        &self,
+        accessed: &mut Vec<(&'a StructPat, Ident)>,
+        i: usize,
+        ts: &mut TokenStream,
+        finish: &dyn Fn(&mut TokenStream),
+    ) {
+        let (struct_pat, local) = match accessed.get(i).cloned() {
+            Some(x) => x,
+            None => return finish(ts),
+        };
+
+        let mut pattern = TokenStream::new();
+        let mut value = TokenStream::new();
+
+        match struct_pat.unsafe_token {
+            Some(unsafe_span) => {
+                // union patterns have exactly one field
+                let FieldPat {
+                    pat_ident,
+                    pattern: field_pattern,
+                    ..
+                } = &struct_pat.elems[0];
+                field_pattern.to_token_stream(&mut pattern, accessed);
+
+                value.append_keyword("unsafe", unsafe_span);
+                tokenize_delim(Delimiter::Brace, struct_pat.group_span, &mut value, |ts| {
+                    ts.append_one(local);
+                    ts.append_one(Punct::new('.', Spacing::Alone).with_span(pat_ident.span()));
+                    ts.append_one(pat_ident.clone());
+                });
+            }
+            None => {
+                for FieldPat {
+                    accessor,
+                    pattern,
+                    type_annotation,
+                    ..
+                } in &struct_pat.elems
+                {
+                    let binding = match (accessor, pattern) {
+                        (Some(_), Pattern::Ident(b)) => b,
+                        // `_` patterns only need to be read to check the type annotation
+                        (Some(_), Pattern::Underscore(b)) if type_annotation.is_some() => b,
+                        _ => continue,
+                    };
+                    let span = binding.local.span();
+
+                    ts.append_keyword("let", span);
+                    ts.append_one(binding.local.clone());
+                    ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+                    ts.append_one(Punct::new('*', Spacing::Alone).with_span(span));
+                    ts.append_one(local.clone());
+                    ts.append_one(Punct::new('.', Spacing::Alone).with_span(span));
+                    ts.extend(accessor.clone());
+                    ts.append_one(
+                        Group::new(Delimiter::Parenthesis, TokenStream::new()).with_span(span),
+                    );
+                    ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+                }
+
+                struct_pat.to_token_stream(&mut pattern, accessed);
+                value.append_one(local);
+            }
+        }
+
+        self.output_destructure(value, pattern, ts, &mut |ts| {
+            self.output_destructured_after(accessed, i + 1, ts, finish)
+        });
+    }
+}
+
+fn tokenize_allow_unused_variables(span: Span, ts: &mut TokenStream) {
+    ts.append_one(Punct::new('#', Spacing::Alone).with_span(span));
+    tokenize_delim(Delimiter::Bracket, span, ts, |ts| {
+        ts.append_keyword("allow", span);
+        tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+            ts.append_keyword("unused_variables", span);
+        });
+    });
+}
+
+// The maximum length of expressions in provenance docs, in chars
+const MAX_DOC_EXPR_LEN: usize = 100;
+
+fn provenance_doc(field_path: &str, expr: &str) -> String {
+    let mut expr_short = String::new();
+    match expr.char_indices().nth(MAX_DOC_EXPR_LEN) {
+        Some((i, _)) => {
+            expr_short.push_str(&expr[..i]);
+            expr_short.push_str("...");
+        }
+        None => expr_short.push_str(expr),
+    }
+
+    let expr = code_span(&expr_short);
+
+    let is_single_index = field_path.starts_with('[')
+        && field_path.ends_with(']')
+        && field_path.matches(']').count() == 1;
+
+    if field_path.is_empty() {
+        format!(" Value of {}", expr)
+    } else if is_single_index {
+        let index = &field_path[1..field_path.len() - 1];
+        let elements = if index.contains("..") {
+            "Elements"
+        } else {
+            "Element"
+        };
+        format!(" {} {} of {}", elements, code_span(index), expr)
+    } else {
+        format!(" Field {} of {}", code_span(field_path), expr)
+    }
+}
+
+fn code_span(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {} ``", code)
+    } else {
+        format!("`{}`", code)
+    }
+}
+
+fn tokenize_doc_hidden(span: Span, ts: &mut TokenStream) {
+    ts.append_one(Punct::new('#', Spacing::Alone).with_span(span));
+    tokenize_delim(Delimiter::Bracket, span, ts, |ts| {
+        ts.append_keyword("doc", span);
+        tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+            ts.append_keyword("hidden", span);
+        });
+    });
+}
+
+fn tokenize_doc_attr(doc: Literal, span: Span, ts: &mut TokenStream) {
+    ts.append_one(Punct::new('#', Spacing::Alone).with_span(span));
+    tokenize_delim(Delimiter::Bracket, span, ts, |ts| {
+        ts.append_keyword("doc", span);
+        ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+        ts.append_one(doc.with_span(span));
+    });
+}
+
+/// Outputs the registry constant,
+/// whose entries for constants with cfgs are only included when the cfgs are enabled.
+#[allow(clippy::too_many_arguments)]
+fn output_registry(
+    crate_kw: &Crate,
+    const_path: &TokenStream,
+    vis: &TokenStream,
+    outer_cfgs: &TokenStream,
+    registry: Ident,
+    value_type: TokenStream,
+    entries: &[(Ident, Option<TokenStream>)],
+    out: &mut ConstOutput,
+) {
+    let span = registry.span();
+
+    let mut attrs = outer_cfgs.clone();
+    attrs.extend(
+        "
+        /// The names of the constants declared alongside this one, paired with their values.
+        "
+        .parse::<TokenStream>()
+        .unwrap(),
+    );
+
+    // the length is the amount of constants without cfgs,
+    // plus `cfg!(...) as usize` for each constant with cfgs.
+    let mut len = TokenStream::new();
+    let uncfgd_count = entries.iter().filter(|(_, pred)| pred.is_none()).count();
+    len.append_one(Literal::usize_unsuffixed(uncfgd_count).with_span(span));
+    for pred in entries.iter().filter_map(|(_, pred)| pred.as_ref()) {
+        len.append_one(Punct::new('+', Spacing::Alone).with_span(span));
+        len.append_one(Ident::new("cfg", span));
+        len.append_one(Punct::new('!', Spacing::Alone).with_span(span));
+        len.append_one(Group::new(Delimiter::Parenthesis, pred.clone()).with_span(span));
+        len.append_keyword("as", span);
+        crate_kw.item_to_ts("usize", Spans::from_one(span), &mut len);
+    }
+
+    let mut type_ = TokenStream::new();
+    tokenize_delim(Delimiter::Bracket, span, &mut type_, |ts| {
+        tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+            ts.append_one(Punct::new('&', Spacing::Alone).with_span(span));
+            ts.append_one(Punct::new('\'', Spacing::Joint).with_span(span));
+            ts.append_keyword("static", span);
+            crate_kw.item_to_ts("str", Spans::from_one(span), ts);
+            syntax::tokenize_comma(span, ts);
+            ts.extend(value_type);
+        });
+        ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+        ts.extend(len);
+    });
+
+    let mut value = TokenStream::new();
+    tokenize_iter_delim(
+        Delimiter::Bracket,
+        span,
+        entries,
+        &mut value,
+        |ts, (constant, pred)| {
+            let cspan = constant.span();
+            if let Some(pred) = pred {
+                tokenize_cfg_attr(pred.clone(), cspan, ts);
+            }
+            tokenize_delim(Delimiter::Parenthesis, cspan, ts, |ts| {
+                let name = crate::utils::ident_to_string_no_raw(constant);
+                ts.append_one(Literal::string(&name).with_span(cspan));
+                syntax::tokenize_comma(cspan, ts);
+                ts.extend(const_path.clone());
+                ts.append_one(constant.clone());
+            });
+            syntax::tokenize_comma(cspan, ts);
+        },
+    );
+
+    out.add_const(attrs, vis, registry, type_, value, false);
+}
+
+/// Outputs a `#[cfg(pred)]` attribute.
+fn tokenize_cfg_attr(pred: TokenStream, span: Span, ts: &mut TokenStream) {
+    ts.append_one(Punct::new('#', Spacing::Alone).with_span(span));
+    tokenize_delim(Delimiter::Bracket, span, ts, |ts| {
+        ts.extend(syntax::cfg_predicate_call("cfg", pred).with_span(span));
+    });
+}
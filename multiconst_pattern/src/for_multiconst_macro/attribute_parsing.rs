@@ -0,0 +1,279 @@
+use used_proc_macro::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    parsing::{ParseBuffer, ParseStream},
+    syntax::Attributes,
+    utils::{IsIdent, TokenStreamExt, TokenTreeExt, WithSpan},
+    Error,
+};
+
+/// The configuration from `#[multiconst(...)]` attributes on a `const` item.
+#[derive(Default)]
+pub(crate) struct MulticonstAttrs {
+    /// The name of the constant that lists all the generated constants.
+    pub(crate) registry: Option<Ident>,
+    /// Whether to document where each constant was destructured from.
+    pub(crate) provenance_docs: bool,
+    /// The templates for documentation that's generated for each constant.
+    pub(crate) doc_templates: Vec<DocTemplate>,
+    /// The `debug` argument, which makes the macro error with the generated code.
+    pub(crate) debug: Option<Ident>,
+}
+
+/// A `doc = "..."` template, with `{index}`, `{field}`, and `{name}` placeholders.
+pub(crate) struct DocTemplate {
+    span: Span,
+    /// Whether this is a raw string literal
+    is_raw: bool,
+    // the start and end of the string literal, eg: `r#"` and `"#`
+    prefix: String,
+    suffix: String,
+    pieces: Vec<TemplatePiece>,
+}
+
+enum TemplatePiece {
+    /// Text in the source code of the string literal, including escapes.
+    Text(String),
+    /// The position of the element that the constant was destructured from
+    Index,
+    /// The path to the destructured field, eg: `.foo[3]`
+    Field,
+    /// The name of the constant
+    Name,
+}
+
+impl DocTemplate {
+    fn parse(lit: Literal) -> Result<Self, Error> {
+        let span = lit.span();
+        let source = lit.to_string();
+        let error = || Error::with_span(span, "expected a string literal");
+
+        let (is_raw, quote_start) = if source.starts_with('"') {
+            (false, 0)
+        } else if source.starts_with('r') {
+            (true, source.find('"').ok_or_else(error)?)
+        } else {
+            return Err(error());
+        };
+        let quote_end = source
+            .rfind('"')
+            .filter(|&x| x > quote_start)
+            .ok_or_else(error)?;
+
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut rem = &source[quote_start + 1..quote_end];
+
+        while let Some(c) = rem.chars().next() {
+            if c == '\\' && !is_raw {
+                // copies escapes verbatim, including the braces of `\u{...}` escapes
+                let len = if rem[1..].starts_with('u') {
+                    rem.find('}').map_or(rem.len(), |x| x + 1)
+                } else {
+                    1 + rem[1..].chars().next().map_or(0, char::len_utf8)
+                };
+                text.push_str(&rem[..len]);
+                rem = &rem[len..];
+                continue;
+            } else if rem.starts_with("{{") || rem.starts_with("}}") {
+                text.push(c);
+                rem = &rem[2..];
+                continue;
+            } else if c == '}' {
+                return Err(Error::with_span(
+                    span,
+                    "unmatched `}` in doc template, use `}}` to write a `}`",
+                ));
+            } else if c != '{' {
+                text.push(c);
+                rem = &rem[c.len_utf8()..];
+                continue;
+            }
+
+            let end = rem.find('}').ok_or_else(|| {
+                Error::with_span(
+                    span,
+                    "unclosed `{` in doc template, use `{{` to write a `{`",
+                )
+            })?;
+
+            let piece = match &rem[1..end] {
+                "index" => TemplatePiece::Index,
+                "field" => TemplatePiece::Field,
+                "name" => TemplatePiece::Name,
+                placeholder => {
+                    return Err(Error::with_span(
+                        span,
+                        alloc::format!(
+                            "unknown `{{{}}}` placeholder in doc template, \
+                             expected one of `{{index}}`, `{{field}}`, or `{{name}}`",
+                            placeholder,
+                        ),
+                    ))
+                }
+            };
+
+            pieces.push(TemplatePiece::Text(core::mem::take(&mut text)));
+            pieces.push(piece);
+            rem = &rem[end + 1..];
+        }
+        pieces.push(TemplatePiece::Text(text));
+
+        Ok(Self {
+            span,
+            is_raw,
+            prefix: source[..=quote_start].to_string(),
+            suffix: source[quote_end..].to_string(),
+            pieces,
+        })
+    }
+
+    /// Outputs the documentation of a constant as a string literal.
+    ///
+    /// `index` is `None` when the constant isn't destructured from an array or tuple element.
+    pub(crate) fn expand(
+        &self,
+        index: Option<&str>,
+        field: &str,
+        name: &str,
+    ) -> Result<Literal, Error> {
+        let mut source = self.prefix.clone();
+
+        for piece in &self.pieces {
+            let value = match piece {
+                TemplatePiece::Text(text) => {
+                    source.push_str(text);
+                    continue;
+                }
+                TemplatePiece::Index => match index {
+                    Some(index) => index.to_string(),
+                    None => {
+                        return Err(Error::with_span(
+                            self.span,
+                            alloc::format!(
+                                "the `{{index}}` placeholder requires `{}` to be destructured \
+                                 from an array or tuple element",
+                                name,
+                            ),
+                        ))
+                    }
+                },
+                TemplatePiece::Field => field.to_string(),
+                TemplatePiece::Name => name.to_string(),
+            };
+
+            if self.is_raw {
+                source.push_str(&value);
+            } else {
+                source.extend(value.chars().flat_map(char::escape_default));
+            }
+        }
+
+        source.push_str(&self.suffix);
+
+        match source
+            .parse::<TokenStream>()
+            .map(|ts| ts.into_iter().next())
+        {
+            Ok(Some(TokenTree::Literal(lit))) => Ok(lit.with_span(self.span)),
+            _ => unreachable!("expanded doc template is not a string literal: {}", source),
+        }
+    }
+}
+
+/// Removes the `#[multiconst(...)]` attributes from `attrs`, parsing them.
+pub(crate) fn parse_multiconst_attrs(attrs: &mut Attributes) -> Result<MulticonstAttrs, Error> {
+    let mut out = MulticonstAttrs::default();
+    let mut kept = TokenStream::new();
+
+    let input = &mut ParseBuffer::new(core::mem::replace(&mut attrs.attrs, TokenStream::new()));
+
+    while let Some(pound) = input.next() {
+        let group = match input.next() {
+            Some(TokenTree::Group(group)) => group,
+            _ => unreachable!("`Attributes` only contains `#[...]` attributes"),
+        };
+
+        let attr = &mut ParseBuffer::with_span(group.stream(), group.span());
+
+        if !matches!(attr.peekn(2), [tt0, tt1] if tt0.is_ident("multiconst") && tt1.is_group(Delimiter::Parenthesis))
+        {
+            kept.append_one(pound);
+            kept.append_one(group);
+            continue;
+        }
+
+        let _ = attr.next();
+        let args = attr.parse_group()?;
+        attr.assert_empty()?;
+
+        parse_multiconst_args(
+            &mut ParseBuffer::with_span(args.stream(), args.span()),
+            &mut out,
+        )?;
+    }
+
+    attrs.attrs = kept;
+
+    Ok(out)
+}
+
+fn parse_multiconst_args(input: ParseStream<'_>, out: &mut MulticonstAttrs) -> Result<(), Error> {
+    while !input.is_empty() {
+        let arg = input.parse_ident()?;
+
+        if arg.is_ident("registry") {
+            input.parse_punct('=')?;
+            let name = input.parse_ident()?;
+
+            if out.registry.is_some() {
+                return Err(Error::with_span(
+                    arg.span(),
+                    "the `registry` argument was already passed",
+                ));
+            }
+            out.registry = Some(name);
+        } else if arg.is_ident("provenance_docs") {
+            if out.provenance_docs {
+                return Err(Error::with_span(
+                    arg.span(),
+                    "the `provenance_docs` argument was already passed",
+                ));
+            }
+            out.provenance_docs = true;
+        } else if arg.is_ident("doc") {
+            input.parse_punct('=')?;
+            let template = match input.next() {
+                Some(TokenTree::Literal(lit)) => DocTemplate::parse(lit)?,
+                Some(tt) => return Err(Error::with_span(tt.span(), "expected a string literal")),
+                None => return Err(input.error("expected a string literal after this")),
+            };
+            out.doc_templates.push(template);
+        } else if arg.is_ident("debug") {
+            if out.debug.is_some() {
+                return Err(Error::with_span(
+                    arg.span(),
+                    "the `debug` argument was already passed",
+                ));
+            }
+            out.debug = Some(arg);
+        } else {
+            return Err(Error::with_span(
+                arg.span(),
+                "unknown `multiconst` attribute argument, \
+                 expected one of `registry`, `provenance_docs`, `doc`, or `debug`",
+            ));
+        }
+
+        if !input.is_empty() {
+            input.parse_punct(',')?;
+        }
+    }
+
+    Ok(())
+}
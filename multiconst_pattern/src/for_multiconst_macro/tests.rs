@@ -0,0 +1,964 @@
+use crate::{for_multiconst_macro::Usedwhere, test_utils::StrExt};
+
+use alloc::string::{String, ToString};
+
+fn process_str(s: &str) -> Result<String, String> {
+    let s = alloc::format!("crate {}", s);
+    let ts = s.parse::<used_proc_macro::TokenStream>().unwrap();
+
+    ::std::dbg!(&ts);
+
+    crate::for_multiconst_macro::macro_impl(ts, Usedwhere::OutsideImpls)
+        .map(|x| x.to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn no_inferred_length_error() {
+    {
+        let out = process_str("const [A, ..]: [u32; _] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["infer", "length", "`..`"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn mismatched_pattern_and_type_constructor() {
+    {
+        let out = process_str("const (A, B): [u32; 0] = expr;").unwrap_err();
+        assert!(out.consecutive_in_self(&["mismatched", "type"]), "{}", out);
+    }
+    {
+        let out = process_str("const [A, B]: (u32, u32) = expr;").unwrap_err();
+        assert!(out.consecutive_in_self(&["mismatched", "type"]), "{}", out);
+    }
+}
+
+#[test]
+fn mismatched_tuple_lengths() {
+    {
+        let out = process_str("const (A, B): () = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["pattern has 2", "but type has 0"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const (A, B): (u32,) = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["pattern has 2", "but type has 1"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const (A, B): (u32, u32, u32) = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["pattern has 2", "but type has 3"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn top_level_remainder_pattern() {
+    {
+        let out = process_str("const ..: u32 = 100;").unwrap_err();
+        assert!(out.contains("`..` patterns"), "{}", out);
+    }
+}
+
+#[test]
+fn attributes_on_patterns() {
+    {
+        let out = process_str("#[foo] const #[bar] A: u32 = 100;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[bar] #[foo] const A: u32"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("#[foo] const (#[bar] A, #[baz] B): (u32, u32) = 100;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[bar] #[foo] const A: u32", "#[baz] #[foo] const B: u32",]),
+            "{}",
+            out,
+        );
+    }
+    {
+        let out = process_str("#[foo] const [#[bar] A, #[baz] B @ ..]: [u32; 3] = 100;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "#[bar] #[foo] const A: u32",
+                "#[baz] #[foo] const B: [u32;",
+            ]),
+            "{}",
+            out,
+        );
+    }
+    {
+        let out = process_str("#[foo] const F{#[bar] a: A}: F = E;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[bar] #[foo] const A:"]),
+            "{}",
+            out,
+        );
+    }
+    {
+        let out = process_str("#[foo] const F(#[bar] A): F = E;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[bar] #[foo] const A:"]),
+            "{}",
+            out,
+        );
+    }
+}
+
+#[test]
+fn visibility_qualifiers() {
+    {
+        let out = process_str("const A: u32 = 100;").unwrap();
+        assert!(!out.contains("pub"), "{}", out);
+    }
+    {
+        let out = process_str("pub const A: u32 = 100;").unwrap();
+        assert_eq!(out.matches("pub").count(), 1, "{}", out);
+        assert!(out.consecutive_unspace(&["pub const A"]), "{}", out);
+    }
+    {
+        let out = process_str("pub(crate) const A: u32 = 100;").unwrap();
+        assert_eq!(out.matches("pub").count(), 1, "{}", out);
+        assert!(out.consecutive_unspace(&["pub(crate) const A"]), "{}", out);
+    }
+    {
+        let out = process_str(
+            "
+            pub(crate) const (A, B): (u32, u64) = 100;
+        ",
+        )
+        .unwrap();
+
+        assert_eq!(out.matches("pub").count(), 2, "{}", out);
+
+        assert!(
+            out.consecutive_unspace(&["pub(crate) const A", "pub(crate) const B"]),
+            "{}",
+            out,
+        );
+    }
+}
+
+#[test]
+fn coercion_annotations() {
+    {
+        let out = process_str("const [A, B @ ..: &[u32]]: [u32; 3] = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["const B: &'static [u32] = (&", ".1)"]),
+            "{}",
+            out
+        );
+    }
+    {
+        // `bytes_to_str` is a macro that errors without the "rust_1_64" feature
+        let out = process_str("const (A: &str, B): ([u8; 2], u8) = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["const A: &'static str = crate::__::bytes_to_str!(&", ".0)"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const [A, _: &[u32]]: [u32; 2] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["coercion", "only", "bindings"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const [A, B: [u32]]: [u32; 2] = expr;").unwrap_err();
+        assert!(out.consecutive_in_self(&["expected", "`&Type`"]), "{}", out);
+    }
+}
+
+#[test]
+fn registry_attribute() {
+    {
+        let out = process_str(
+            "#[multiconst(registry = ALL)] #[doc = \"foo\"] pub const (A, r#B): (u32, u32) = expr;",
+        )
+        .unwrap();
+        assert!(!out.contains("multiconst ("), "{}", out);
+        assert!(
+            out.consecutive_unspace(&[
+                "pub const ALL: [(&'static crate::__::str, u32); 2] =",
+                "[(\"A\", A), (\"B\", r#B),];"
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("#[multiconst(registry = ALL)] const (_, _): (u32, u32) = expr;")
+            .unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["registry", "at least one constant"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("#[multiconst(foo)] const A: u32 = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["unknown", "expected", "`registry`"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn provenance_docs_attribute() {
+    let doc_of = |out: &str, constant: &str| -> String {
+        let end = out
+            .find(&alloc::format!("const {} :", constant))
+            .unwrap_or_else(|| panic!("{}", out));
+        let start = out[..end].rfind("# [doc = \"").unwrap() + 10;
+        out[start..end].split('"').next().unwrap().to_string()
+    };
+
+    {
+        let out = process_str(
+            "#[multiconst(provenance_docs)] \
+             const [A, B, REST @ .., LAST]: [u32; 10] = seq::powers_of_two::<u32, 10>();",
+        )
+        .unwrap();
+        let expr = "`seq::powers_of_two::<u32, 10>()`";
+        assert_eq!(
+            doc_of(&out, "A"),
+            alloc::format!(" Element `0` of {}", expr)
+        );
+        assert_eq!(
+            doc_of(&out, "B"),
+            alloc::format!(" Element `1` of {}", expr)
+        );
+        assert_eq!(
+            doc_of(&out, "REST"),
+            alloc::format!(" Elements `2..len - 1` of {}", expr)
+        );
+        assert_eq!(
+            doc_of(&out, "LAST"),
+            alloc::format!(" Element `len - 1` of {}", expr)
+        );
+    }
+    {
+        let out = process_str(
+            "#[multiconst(provenance_docs)] \
+             const Config{limits: Limits{max: MAX, ..}, names: (NAME, [_, ALIAS]), ..}: \
+             Config = config(&[1, -2], a.b + c);",
+        )
+        .unwrap();
+        let expr = "`config(&[1, -2], a.b + c)`";
+        assert_eq!(
+            doc_of(&out, "MAX"),
+            alloc::format!(" Field `.limits.max` of {}", expr)
+        );
+        assert_eq!(
+            doc_of(&out, "NAME"),
+            alloc::format!(" Field `.names.0` of {}", expr)
+        );
+        assert_eq!(
+            doc_of(&out, "ALIAS"),
+            alloc::format!(" Field `.names.1[1]` of {}", expr)
+        );
+    }
+    {
+        let out = process_str(
+            "#[multiconst(provenance_docs)] const (A, .., B): (u8, u16, u32, u64) = FOO;",
+        )
+        .unwrap();
+        assert_eq!(doc_of(&out, "A"), " Field `.0` of `FOO`");
+        assert_eq!(doc_of(&out, "B"), " Field `.3` of `FOO`");
+    }
+    {
+        let out = process_str("#[multiconst(provenance_docs)] const A: u8 = 1 + 2;").unwrap();
+        assert_eq!(doc_of(&out, "A"), " Value of `1 + 2`");
+    }
+    {
+        let out = process_str("const [A, B]: [u8; 2] = FOO;").unwrap();
+        assert!(!out.contains("doc"), "{}", out);
+    }
+}
+
+#[test]
+fn doc_template_attribute() {
+    {
+        let out = process_str(
+            r##"#[multiconst(doc = "Register {index} (`{field}`, {name}) {{index}}")]
+            #[multiconst(doc = r#"line "two" {name}"#)]
+            const [R0, _, r#R2]: [u8; 3] = FOO;"##,
+        )
+        .unwrap();
+        assert!(!out.contains("multiconst ("), "{}", out);
+        assert!(
+            out.consecutive_unspace(&[
+                "#[doc = \"Register 0 (`[0]`, R0) {index}\"]",
+                "#[doc = r#\"line \"two\" R0\"#]",
+                "const R0",
+            ]),
+            "{}",
+            out
+        );
+        // `{index}` is the position in the array, so the `_` pattern is counted
+        assert!(
+            out.consecutive_unspace(&[
+                "#[doc = \"Register 2 (`[2]`, R2) {index}\"]",
+                "#[doc = r#\"line \"two\" R2\"#]",
+                "const r#R2",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        // `{index}` is the position of the innermost array or tuple element,
+        // and elements after `..` are indexed from the end
+        // when the length of the sequence isn't known.
+        let out = process_str(
+            r#"#[multiconst(doc = "{index}")]
+            const ([A, .., B], (C, .., Foo{x: D}), [E, .., F]): ([u8; 4], Tup, Arr) = FOO;"#,
+        )
+        .unwrap();
+        for (name, index) in [
+            ("A", "0"),
+            ("B", "3"),
+            ("C", "0"),
+            ("D", "len - 1"),
+            ("E", "0"),
+            ("F", "len - 1"),
+        ]
+        .iter()
+        {
+            let doc = alloc::format!("#[doc = \"{}\"] const {}:", index, name);
+            assert!(out.consecutive_unspace(&[&doc]), "{}\n{}", doc, out);
+        }
+    }
+    {
+        // cfg-ed constants don't shift the index of the others
+        let out = process_str(
+            r#"#[multiconst(doc = "{index}")]
+            const [#[cfg(foo)] A, B]: [u8; 2] = FOO;"#,
+        )
+        .unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[doc = \"1\"] const B:"]),
+            "{}",
+            out
+        );
+    }
+    for (template, err) in [
+        ("\"{foo}\"", &["unknown `{foo}` placeholder"][..]),
+        ("\"{index\"", &["unclosed `{`"]),
+        ("\"{n}\"", &["unknown `{n}` placeholder"]),
+        ("\"index}\"", &["unmatched `}`"]),
+        ("b\"bytes\"", &["expected a string literal"]),
+        ("100", &["expected a string literal"]),
+    ]
+    .iter()
+    {
+        let out = process_str(&alloc::format!(
+            "#[multiconst(doc = {})] const A: u8 = FOO;",
+            template
+        ))
+        .unwrap_err();
+        assert!(out.consecutive_in_self(err), "{}", out);
+    }
+    for pattern in &["A: u8", "Foo{x: A}: Foo"] {
+        let out = process_str(&alloc::format!(
+            "#[multiconst(doc = \"{{index}}\")] const {} = FOO;",
+            pattern
+        ))
+        .unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["`{index}`", "requires `A`", "array or tuple element"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn cfg_attributes() {
+    {
+        let out = process_str(
+            "#[cfg(feature = \"foo\")] #[doc = \"hello\"] \
+             const (A, #[cfg(feature = \"bar\")] B, C): (u8, u16, u32) = expr;",
+        )
+        .unwrap();
+
+        // the expression is evaluated once, in a constant that the others destructure
+        assert_eq!(out.matches("= expr").count(), 1, "{}", out);
+        assert!(
+            out.consecutive_unspace(&[
+                "#[cfg(feature = \"foo\")] const __PRIV_MULTICONST_VALUE__A: (u8, u16, u32,) = expr;",
+                "#[cfg(feature = \"foo\")] #[allow(unused_variables)] const __PRIV_MULTICONST__A: (u8, u32,)",
+                "= __PRIV_MULTICONST_VALUE__A;",
+                "(A__local_variable1, C__local_variable3,)",
+                "#[cfg(feature = \"foo\")] #[cfg(feature = \"bar\")] #[allow(unused_variables)] \
+                 const __PRIV_MULTICONST_CFG__B: (u16,)",
+                "= __PRIV_MULTICONST_VALUE__A;",
+                "(B__local_variable2,)",
+                "const B: u16 = __PRIV_MULTICONST_CFG__B.0;",
+                "const C: u32 = __PRIV_MULTICONST__A.1;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        // `cfg_attr`s that don't expand to `cfg`s don't make the constant conditional
+        let out = process_str(
+            "const (A, #[cfg_attr(feature = \"bar\", doc = \"bar\")] B): (u8, u16) = expr;",
+        )
+        .unwrap();
+        assert!(!out.contains("VALUE"), "{}", out);
+        assert!(!out.contains("CFG"), "{}", out);
+        assert!(
+            out.consecutive_unspace(&["const B: u16 = __PRIV_MULTICONST__A.1;"]),
+            "{}",
+            out
+        );
+    }
+    {
+        // the hidden constants are named after the first constant without cfgs
+        let out = process_str("const (#[cfg(a)] A, B): (u8, u16) = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const __PRIV_MULTICONST_VALUE__B",
+                "const __PRIV_MULTICONST__B: (u16,)",
+                "const __PRIV_MULTICONST_CFG__A: (u8,)",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        // the expression is only evaluated if any constant is enabled
+        let out = process_str("const (#[cfg(a)] A, #[cfg(b)] B): (u8, u16) = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["#[cfg(any(a, b,))] const __PRIV_MULTICONST_VALUE__A",]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str(
+            "#[multiconst(registry = ALL)] \
+             const (A, #[cfg(a)] B, #[cfg_attr(b, cfg(c))] C): (u8, u8, u8) = expr;",
+        )
+        .unwrap();
+        assert_eq!(out.matches("const ALL").count(), 1, "{}", out);
+        assert!(
+            out.consecutive_unspace(&[
+                "const ALL: [(&'static crate::__::str, u8); \
+                 1 + cfg!(a) as crate::__::usize + cfg!(any(not(b), c,)) as crate::__::usize] =",
+                "[(\"A\", A), #[cfg(a)] (\"B\", B), #[cfg(any(not(b), c,))] (\"C\", C),];",
+            ]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn module_syntax() {
+    {
+        let out =
+            process_str("#[doc = \"a\"] pub mod foo { const (A, B): (u8, u8) = expr; }").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "#[doc = \"a\"] pub mod foo {",
+                "#[allow(unused_imports)] use super::*;",
+                "const __PRIV_MULTICONST__A",
+                "const A: u8 = __PRIV_MULTICONST__A.0;",
+                "}",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("mod foo;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["expected", "module contents"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let ts = "crate mod foo { const A: u8 = 0; }"
+            .parse::<used_proc_macro::TokenStream>()
+            .unwrap();
+        let out = crate::for_multiconst_macro::macro_impl(ts, Usedwhere::InherentImpl)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            out.consecutive_in_self(&["modules", "inside impl blocks"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn extension_trait() {
+    let process_ext = |s: &str| {
+        let ts = alloc::format!("crate {}", s)
+            .parse::<used_proc_macro::TokenStream>()
+            .unwrap();
+        crate::for_multiconst_macro::extension_macro_impl(ts)
+            .map(|x| x.to_string())
+            .map_err(|e| e.to_string())
+    };
+
+    {
+        let out = process_ext(
+            "#[doc = \"a\"] pub trait Limits for Foo<u8> { \
+                 #[deprecated] const (#[doc = \"b\"] LO, HI): (u32, u32) = expr; \
+             }",
+        )
+        .unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "#[doc = \"a\"] pub trait Limits {",
+                "#[doc(hidden)] const __PRIV_MULTICONST__LO: (u32, u32,);",
+                "#[doc = \"b\"] #[deprecated] const LO: u32;",
+                "#[deprecated] const HI: u32;",
+                "}",
+                "impl Limits for Foo<u8> {",
+                "const __PRIV_MULTICONST__LO: (u32, u32,) = {",
+                "const LO: u32 = Self::__PRIV_MULTICONST__LO.0;",
+                "const HI: u32 = Self::__PRIV_MULTICONST__LO.1;",
+                "}",
+            ]),
+            "{}",
+            out
+        );
+        assert!(!out.contains("deprecated] const LO: u32 ="), "{}", out);
+    }
+    {
+        let out = process_ext("trait Limits for u32 { pub const A: u8 = 0; }").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["can't have visibility qualifiers"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_ext("trait Limits for u32 { mod foo {} }").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["modules", "inside traits"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_ext("trait Limits for { }").unwrap_err();
+        assert!(out.consecutive_in_self(&["expected the type"]), "{}", out);
+    }
+}
+
+#[test]
+fn slice_patterns() {
+    {
+        let out = process_str("const (A, [B, _, C @ ..]): (u8, &[u16]) = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const __PRIV_MULTICONST__A: (u8, u16, &'static [u16],) = {",
+                "let __multiconst_value: (u8, &'static [u16],) = expr;",
+                "match __multiconst_value {",
+                "(A__local_variable1, [B__local_variable2, ___local_variable3, C__local_variable4 @ ..,],) => {",
+                "let _: &u16 = ___local_variable3;",
+                "(A__local_variable1, crate::__::copy_slice_element!(B__local_variable2), C__local_variable4,)",
+                "}",
+                "_ => crate::__::slice_length_mismatch!(),",
+                "const A: u8 = __PRIV_MULTICONST__A.0;",
+                "const B: u16 = __PRIV_MULTICONST__A.1;",
+                "const C: &'static [u16] = __PRIV_MULTICONST__A.2;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        // slice types are only special-cased when written syntactically
+        let out = process_str("const A: &'a [u8] = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["let A__local_variable1: &'a [u8] = expr;"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const (A, B): &[u8] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["mismatched pattern and type"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn debug_attribute() {
+    {
+        let out = process_str(
+            "const X: u8 = 0; \
+             #[multiconst(debug)] const (A, [B, ..]): (u32, [u8; 3]) = (3, FOO);",
+        )
+        .unwrap();
+
+        // the constants are still output, so that errors in them are reported
+        assert!(out.consecutive_unspace(&["const X: u8"]), "{}", out);
+        assert!(out.consecutive_unspace(&["const A: u32"]), "{}", out);
+
+        let msg = &out[out.find("compile_error").unwrap()..];
+        assert!(
+            msg.contains(concat!(
+                "const __PRIV_MULTICONST__A: (u32, u8,) = {\\n",
+                "    let (A__local_variable1, [B__local_variable2, ..,],): (u32, [u8; 3],) = (3, FOO);\\n",
+                "    (A__local_variable1, B__local_variable2,)\\n",
+                "};\\n",
+                "const A: u32 = __PRIV_MULTICONST__A.0;\\n",
+                "const B: u8 = __PRIV_MULTICONST__A.1;\\n",
+            )),
+            "{}",
+            msg
+        );
+        // only the code generated for the constant with the `debug` argument is shown
+        assert!(!msg.contains("const X"), "{}", msg);
+    }
+    {
+        let out = process_str("const [A, B]: [u8; 2] = FOO;").unwrap();
+        assert!(!out.contains("compile_error"), "{}", out);
+    }
+    {
+        let out = process_str("#[multiconst(debug, debug)] const A: u8 = FOO;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["`debug`", "already passed"]),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn multiple_errors() {
+    let out = process_str(
+        "const [A B, C, D @ .., E @ ..]: [u8; 4] = FOO; \
+         const X: u8 = 0; \
+         const Foo{a F, b: G, .., c: H}: Foo = BAR; \
+         #[multiconst(unknown)] const Y: u8 = 1; \
+         pub mod foo { const (I, J K): (u8, u8) = BAZ; } \
+         const Z: u8 = 2;",
+    )
+    .unwrap_err();
+
+    assert!(
+        out.consecutive_in_self(&[
+            "expected a `,`",
+            "cannot use `..` multiple times in array patterns",
+            "expected a `:`",
+            "only trailing `..` patterns are supported in structs",
+            "unknown `multiconst` attribute argument",
+            "expected a `,`",
+        ]),
+        "{}",
+        out
+    );
+    assert_eq!(out.matches("compile_error").count(), 6, "{}", out);
+}
+
+#[test]
+fn duplicate_names() {
+    for (input, name) in [
+        ("const (A, [B, A]): (u8, [u8; 2]) = FOO;", "A"),
+        ("const (A, Foo{x: r#B, y: B}): (u8, Foo) = FOO;", "B"),
+        (
+            "const [A, B]: [u8; 2] = FOO; const (C, B): (u8, u8) = BAR;",
+            "B",
+        ),
+        (
+            "#[multiconst(registry = A)] const [A, B]: [u8; 2] = FOO;",
+            "A",
+        ),
+        (
+            "#[multiconst(registry = ALL)] const A: u8 = 0; const ALL: u8 = 1;",
+            "ALL",
+        ),
+    ]
+    .iter()
+    {
+        let out = process_str(input).unwrap_err();
+        assert!(
+            out.consecutive_in_self(&[
+                &alloc::format!("the `{}` constant is declared multiple times", name)[..],
+                &alloc::format!("`{}` is first declared here", name),
+            ]),
+            "{}",
+            out
+        );
+    }
+
+    for input in [
+        "const __PRIV_MULTICONST__A: u8 = 0;",
+        "const (A, [B, __PRIV_MULTICONST_LENS__B]): (u8, [u8; 2]) = FOO;",
+        "#[multiconst(registry = __PRIV_MULTICONST__ALL)] const A: u8 = 0;",
+        "const (A, #[cfg(a)] __PRIV_MULTICONST_CFG__B): (u8, u8) = FOO;",
+    ]
+    .iter()
+    {
+        let out = process_str(input).unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["`__PRIV_MULTICONST_`", "reserved"]),
+            "{}",
+            out
+        );
+    }
+
+    // constants with the same name that aren't enabled at the same time,
+    // or are in different modules.
+    for input in [
+        "const (#[cfg(a)] A, #[cfg(not(a))] A): (u8, u8) = FOO;",
+        "#[cfg(a)] const A: u8 = 0; #[cfg(not(a))] const A: u8 = 1;",
+        "const A: u8 = 0; mod foo { const A: u8 = 1; }",
+    ]
+    .iter()
+    {
+        process_str(input).unwrap();
+    }
+}
+
+#[test]
+fn union_patterns() {
+    {
+        let out = process_str("const (A, unsafe Foo{x: B}): (u8, Foo) = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let (A__local_variable1, __multiconst_struct0,): (u8, Foo,) = FOO;",
+                "let B__local_variable2 = unsafe { __multiconst_struct0.x };",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        // only the fields of union patterns marked with `unsafe` are read in `unsafe` blocks
+        let out = process_str("const (unsafe U{a: A}, U{b: B}): (U, U) = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let (__multiconst_struct0, U { b: B__local_variable2, },): (U, U,) = FOO;",
+                "let A__local_variable1 = unsafe { __multiconst_struct0.a };",
+            ]),
+            "{}",
+            out
+        );
+        assert_eq!(out.matches("unsafe").count(), 1, "{}", out);
+    }
+    {
+        let out = process_str("const unsafe U{a: unsafe V{b: (A, _)}}: U = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let __multiconst_struct0: U = FOO;",
+                "let __multiconst_struct1 = unsafe { __multiconst_struct0.a };",
+                "let (A__local_variable1, ___local_variable2,) = \
+                 unsafe { __multiconst_struct1.b };",
+            ]),
+            "{}",
+            out
+        );
+    }
+
+    for (input, err) in [
+        (
+            "const unsafe Foo{x: A, ..}: Foo = FOO;",
+            "`..` can't be used in union patterns",
+        ),
+        (
+            "const unsafe Foo{x: A, y: B}: Foo = FOO;",
+            "exactly one `{}`-delimited field",
+        ),
+        (
+            "const unsafe Foo(A): Foo = FOO;",
+            "exactly one `{}`-delimited field",
+        ),
+    ]
+    .iter()
+    {
+        let out = process_str(input).unwrap_err();
+        assert!(out.contains(err), "{}", out);
+    }
+}
+
+#[test]
+fn array_rem_length() {
+    {
+        let out = process_str("const [A, B @ .., C]: Foo = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&["array_rem_length(<(Foo) as", "SeqLength>::LENGTH, 2)"]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const [A, B @ .., C]: [u8; 5] = FOO;").unwrap();
+        assert!(!out.contains("array_rem_length"), "{}", out);
+        assert!(out.consecutive_unspace(&["[u8; 5 - 2]"]), "{}", out);
+    }
+}
+
+#[test]
+fn array_elem_assertions() {
+    {
+        let out = process_str("const [A, B @ .., C]: Foo = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const _: () = { let () = <Foo as",
+                "FieldType<",
+                "Usize<1>",
+                ">>::ASSERT_EXISTS;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const (A, [B, .., C, D]): (u8, Foo) = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let () = <Foo as",
+                "FieldType<",
+                "Usize<2>",
+                ">>::ASSERT_EXISTS;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    for input in [
+        "const [A, B @ ..]: [u8; 5] = FOO;",
+        "const [A @ ..]: Foo = FOO;",
+        "const (A, [B, C]): (u8, [u8; 2]) = FOO;",
+    ]
+    .iter()
+    {
+        let out = process_str(input).unwrap();
+        assert!(!out.contains("ASSERT_EXISTS"), "{}", out);
+    }
+    {
+        let ts = "crate const [A, B]: Foo = FOO;"
+            .parse::<used_proc_macro::TokenStream>()
+            .unwrap();
+        let out = crate::for_multiconst_macro::macro_impl(ts, Usedwhere::InherentImpl)
+            .unwrap()
+            .to_string();
+        assert!(!out.contains("ASSERT_EXISTS"), "{}", out);
+    }
+}
+
+#[test]
+fn string_field_names() {
+    {
+        let out = process_str("const (A, Foo{x: B, \"r#type\": C}): (u8, Foo) = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let (A__local_variable1, __multiconst_struct0,): (u8, Foo,) = FOO;",
+                "let C__local_variable3 = *__multiconst_struct0.__multiconst_field_type();",
+                "let Foo { x: B__local_variable2, .. } = __multiconst_struct0;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const Foo{x: Bar{\"1\": A, ..}, \"0\": B}: Foo = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let __multiconst_struct0: Foo = FOO;",
+                "let B__local_variable2 = *__multiconst_struct0.__multiconst_field_0();",
+                "let Foo { x: __multiconst_struct1, .. } = __multiconst_struct0;",
+                "let A__local_variable1 = *__multiconst_struct1.__multiconst_field_1();",
+                "let Bar { .. } = __multiconst_struct1;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = process_str("const Foo{\"0\": _: u8, \"1\": _}: Foo = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "let ___local_variable1 = *__multiconst_struct0.__multiconst_field_0();",
+                "let Foo { .. } = __multiconst_struct0;",
+            ]),
+            "{}",
+            out
+        );
+        assert!(!out.contains("__multiconst_field_1"), "{}", out);
+    }
+    {
+        let out = process_str("const Foo{\"x\": A, y: [B, ..]: &[u8]}: Foo = FOO;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "match __multiconst_value {",
+                "__multiconst_struct0 => {",
+                "let A__local_variable1 = *__multiconst_struct0.__multiconst_field_x();",
+                "match __multiconst_struct0 {",
+                "Foo { y: [B__local_variable2, ..,], .. } => {",
+                "(A__local_variable1, crate::__::copy_slice_element!(B__local_variable2),)",
+                "}",
+                "#[allow(unreachable_patterns)] _ => crate::__::slice_length_mismatch!(),",
+            ]),
+            "{}",
+            out
+        );
+    }
+
+    for (input, err) in [
+        (
+            "const Foo{\"x\": (A, B)}: Foo = FOO;",
+            "can only be bound to constants or `_`",
+        ),
+        (
+            "const unsafe Foo{\"x\": A}: Foo = FOO;",
+            "can't be used in union patterns",
+        ),
+        (
+            "const Foo{\"x y\": A}: Foo = FOO;",
+            "expected a string literal",
+        ),
+        (
+            "const Foo{\"\": A}: Foo = FOO;",
+            "expected a string literal",
+        ),
+        (
+            "const Foo{\"_\": A}: Foo = FOO;",
+            "expected a string literal",
+        ),
+        (
+            "const Foo{r\"x\": A}: Foo = FOO;",
+            "expected a string literal",
+        ),
+    ]
+    .iter()
+    {
+        let out = process_str(input).unwrap_err();
+        assert!(out.contains(err), "{}", out);
+    }
+}
@@ -0,0 +1,101 @@
+//! The destructuring patterns of [`multiconst`], for use in proc macros.
+//!
+//! This crate exposes the parsing, type inference, and expansion of the
+//! patterns that the `multiconst` macros use, with a [`proc_macro2`] API.
+//!
+//! The code generated by [`infer_types`] and [`expand_to`] refers to items in
+//! the `multiconst` crate, so crates that use the generated code must depend on it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use multiconst_pattern::{infer_types, parse_pattern};
+//!
+//! use proc_macro2::{Ident, Span, TokenStream};
+//!
+//! let pattern = parse_pattern("[A, (B, _), C @ ..]".parse().unwrap()).unwrap();
+//!
+//! let multiconst = Ident::new("multiconst", Span::call_site());
+//! let type_ = "[(u8, u16); 4]".parse::<TokenStream>().unwrap();
+//! let typed = infer_types(&multiconst, &pattern, type_).unwrap();
+//!
+//! let names = typed
+//!     .bindings()
+//!     .iter()
+//!     .map(|b| (b.name().to_string(), b.field_path().to_string()))
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(
+//!     names,
+//!     [
+//!         ("A".to_string(), "[0]".to_string()),
+//!         ("B".to_string(), "[1].0".to_string()),
+//!         ("C".to_string(), "[2..]".to_string()),
+//!     ],
+//! );
+//!
+//! ```
+//!
+//! # Features
+//!
+//! - `"proc-macro2"` (enabled by default): enables the API of this crate.
+//!   Without it, this crate only contains the implementation of the
+//!   `multiconst` macros, which doesn't depend on `proc_macro2`.
+//!
+//! [`multiconst`]: https://docs.rs/multiconst/
+#![no_std]
+#![deny(unused_must_use)]
+#![forbid(unsafe_code)]
+#![cfg_attr(
+    not(any(feature = "proc-macro2", feature = "__macros")),
+    allow(dead_code)
+)]
+
+extern crate alloc;
+
+// The macros in `multiconst_proc_macros` use `proc_macro` directly,
+// so that they don't depend on `proc_macro2` unless the `derive` feature is enabled.
+#[cfg(not(any(feature = "proc-macro2", test)))]
+extern crate proc_macro as used_proc_macro;
+
+#[cfg(any(feature = "proc-macro2", test))]
+extern crate proc_macro2 as used_proc_macro;
+
+#[cfg(any(feature = "__dbg", test))]
+extern crate std;
+
+#[cfg(feature = "proc-macro2")]
+mod api;
+
+#[cfg(feature = "__derive")]
+mod derive_macro;
+
+mod error;
+mod for_enum_macro;
+mod for_field_macros;
+mod for_multiconst_macro;
+mod parsing;
+mod pattern;
+mod pattern_processing;
+mod syntax;
+mod type_;
+mod utils;
+
+#[cfg(test)]
+mod test_utils;
+
+#[cfg(all(test, feature = "proc-macro2"))]
+mod tests;
+
+#[cfg(feature = "__macros")]
+#[doc(hidden)]
+pub mod __private;
+
+#[cfg(feature = "proc-macro2")]
+pub use crate::{
+    api::{expand_to, infer_types, parse_pattern, Binding, Location, Pattern, TypedPattern},
+    error::Error,
+};
+
+#[cfg(not(feature = "proc-macro2"))]
+use crate::error::Error;
@@ -61,7 +61,7 @@ impl ParseBuffer {
         Error::with_span(self.last_span(), msg)
     }
 
-    fn is_type_terminator(tt: &TokenTree) -> bool {
+    pub(crate) fn is_type_terminator(tt: &TokenTree) -> bool {
         matches!(
             tt,
             TokenTree::Punct(punct)
@@ -229,6 +229,21 @@ impl ParseBuffer {
         }
     }
 
+    /// Skips tokens until after the next `,` that isn't inside `<>`,
+    /// to continue parsing the elements of a comma-separated list after an error.
+    pub(crate) fn skip_past_comma(&mut self) {
+        let mut level = 0usize;
+
+        for tt in self.by_ref() {
+            match tt {
+                TokenTree::Punct(p) if p.as_char() == '<' => level += 1,
+                TokenTree::Punct(p) if p.as_char() == '>' => level = level.saturating_sub(1),
+                TokenTree::Punct(p) if p.as_char() == ',' && level == 0 => return,
+                _ => {}
+            }
+        }
+    }
+
     pub(crate) fn tokens_until<F>(&mut self, mut func: F) -> TokenStream
     where
         F: FnMut(&TokenTree) -> bool,
@@ -2,13 +2,18 @@ use used_proc_macro::{
     Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
 };
 
-use alloc::{format, string::String, vec::Vec};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use crate::{
+    error::ErrorAccumulator,
     parsing::{ParseBuffer, ParseStream},
     syntax::{self, tokenize_delim, Attributes, FieldName, OpaqueType, Path, Spans},
     type_::{ParsedType, RealType},
-    utils::{ident_to_string_no_raw, TokenStreamExt, TokenTreeExt, WithSpan},
+    utils::{ident_to_string_no_raw, IsIdent, TokenStreamExt, TokenTreeExt, WithSpan},
     Error,
 };
 
@@ -26,6 +31,16 @@ pub(crate) struct BindingAndType {
     // that the pattern is destructured into.
     pub(crate) local: Ident,
     pub(crate) type_: OpaqueType,
+    pub(crate) coercion: Option<Coercion>,
+    // the path to the destructured value, relative to the whole value,
+    // eg: `.foo[2]`, or empty for a binding of the whole value.
+    pub(crate) field_path: String,
+    // the position of the innermost array or tuple element that the value is destructured from,
+    // eg: `2`, or `len - 1` for elements after a `..` in a sequence of unknown length.
+    pub(crate) element_index: Option<String>,
+    // whether the local variable is a reference to the destructured value,
+    // which happens for the values that are destructured from slices.
+    pub(crate) by_ref: bool,
 }
 
 #[derive(Clone)]
@@ -40,6 +55,7 @@ pub(crate) struct Binding {
     // the generated identifier for the temporary variable
     // that the pattern is destructured into.
     pub(crate) local: Ident,
+    pub(crate) coercion: Option<Coercion>,
 }
 
 impl Binding {
@@ -48,16 +64,45 @@ impl Binding {
             attrs,
             constant,
             local,
+            coercion,
         } = self.clone();
         BindingAndType {
             attrs,
             constant,
             local,
             type_,
+            coercion,
+            field_path: String::new(),
+            element_index: None,
+            by_ref: false,
         }
     }
 }
 
+/// A `: &Type` annotation after a binding,
+/// which makes the generated constant a `&'static Type` reference to the destructured value.
+#[derive(Clone)]
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+pub(crate) struct Coercion {
+    pub(crate) ampersand: Span,
+    /// The type after the `&` (and the optional lifetime)
+    pub(crate) type_: OpaqueType,
+    /// Whether the referent type is `str`,
+    /// which requires converting from a byte array.
+    pub(crate) is_str: bool,
+}
+
+impl Coercion {
+    /// Outputs the `&'static Type` type
+    pub(crate) fn type_to_token_stream(&self, ts: &mut TokenStream) {
+        let span = self.ampersand;
+        ts.append_one(Punct::new('&', Spacing::Alone).with_span(span));
+        ts.append_one(Punct::new('\'', Spacing::Joint).with_span(span));
+        ts.append_one(Ident::new("static", span));
+        ts.extend(self.type_.ty.clone());
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 #[cfg_attr(feature = "__dbg", derive(Debug))]
@@ -96,6 +141,8 @@ pub(crate) struct TuplePat {
 
 #[cfg_attr(feature = "__dbg", derive(Debug))]
 pub(crate) struct StructPat {
+    /// The `unsafe` keyword before union patterns, which read a field of the union.
+    pub(crate) unsafe_token: Option<Span>,
     pub(crate) path: Path,
     pub(crate) group_span: Span,
     pub(crate) elems: Vec<FieldPat>,
@@ -109,6 +156,9 @@ pub(crate) struct FieldPat {
     pub(crate) pat_ident: TokenTree,
     // The name used in FieldType
     pub(crate) name: FieldName,
+    // The method that reads the field, for fields named with string literals,
+    // which can't be destructured by name.
+    pub(crate) accessor: Option<Ident>,
     pub(crate) pattern: Pattern,
     pub(crate) type_annotation: Option<RealType>,
 }
@@ -183,10 +233,14 @@ impl Pattern {
                         if p2.as_char() == ':' && p2.spacing() == Spacing::Alone
                     ) =>
                 {
-                    parse_struct_pat(input, state, attrs.take())
+                    parse_struct_pat(input, state, attrs.take(), None)
                 }
                 _ => make_err(),
             },
+            [TT::Ident(ident), ..] if ident.is_ident("unsafe") => {
+                let unsafe_token = input.next().unwrap().span();
+                parse_union_pat(input, state, attrs.take(), unsafe_token)
+            }
             [TT::Ident(ident), rem @ ..] => {
                 match rem {
                     [TT::Punct(p0), ..] if p0.as_char() == '@' => {
@@ -211,9 +265,9 @@ impl Pattern {
                             && p1.as_char() == ':'
                             && p1.spacing() == Spacing::Alone =>
                     {
-                        parse_struct_pat(input, state, attrs.take())
+                        parse_struct_pat(input, state, attrs.take(), None)
                     }
-                    [TT::Group(_), ..] => parse_struct_pat(input, state, attrs.take()),
+                    [TT::Group(_), ..] => parse_struct_pat(input, state, attrs.take(), None),
                     _ => {
                         let mut as_string = ident_to_string_no_raw(ident);
 
@@ -251,16 +305,46 @@ impl Pattern {
             _ => make_err(),
         };
 
-        attrs.ensure_used()?;
+        // the attributes are only unused because of the error in the pattern
+        if ret.is_ok() {
+            attrs.ensure_used()?;
+        }
 
         ret
     }
 }
 
+// parses an `unsafe Union{field: pattern}` pattern
+fn parse_union_pat(
+    input: ParseStream<'_>,
+    state: &mut ParseState,
+    attrs: Attributes,
+    unsafe_token: Span,
+) -> Result<Pattern, Error> {
+    let pattern = parse_struct_pat(input, state, attrs, Some(unsafe_token))?;
+
+    if let Pattern::Struct(StructPat {
+        elems, rem, spans, ..
+    }) = &pattern
+    {
+        if let Some(rem) = rem {
+            return Err(Error::new(*rem, "`..` can't be used in union patterns"));
+        } else if elems.len() != 1 || !matches!(elems[0].name, FieldName::Alphabetic(..)) {
+            return Err(Error::new(
+                *spans,
+                "union patterns must have exactly one `{}`-delimited field",
+            ));
+        }
+    }
+
+    Ok(pattern)
+}
+
 fn parse_struct_pat(
     input: ParseStream<'_>,
     state: &mut ParseState,
     attrs: Attributes,
+    unsafe_token: Option<Span>,
 ) -> Result<Pattern, Error> {
     let path = Path::parse(input)?;
     let group_span: Span;
@@ -281,9 +365,18 @@ fn parse_struct_pat(
                     })?
                 }
                 Delimiter::Brace => parse_struct_fields(&group, state, &attrs, |_, input| {
-                    let ident = input.parse_ident()?;
+                    let (name, pat_ident) = match input.peek() {
+                        Some(TokenTree::Literal(lit)) => {
+                            let name = FieldName::from_str_literal(lit)?;
+                            (name, input.next().unwrap())
+                        }
+                        _ => {
+                            let ident = input.parse_ident()?;
+                            (FieldName::from_ident(&ident), TokenTree::Ident(ident))
+                        }
+                    };
                     input.parse_punct(':')?;
-                    Ok((FieldName::from_ident(&ident), TokenTree::Ident(ident)))
+                    Ok((name, pat_ident))
                 })?,
                 _ => return Err(Error::with_span(group.span(), ERR)),
             }
@@ -298,9 +391,22 @@ fn parse_struct_pat(
         return attrs.unused_error();
     }
 
+    if let (Some(unsafe_token), Some(accessor)) =
+        (unsafe_token, elems.iter().find_map(|e| e.accessor.as_ref()))
+    {
+        return Err(Error::new(
+            Spans {
+                start: unsafe_token,
+                end: accessor.span(),
+            },
+            "fields named with string literals can't be used in union patterns",
+        ));
+    }
+
     Ok(Pattern::Struct(StructPat {
+        unsafe_token,
         spans: Spans {
-            start: path.spans.start,
+            start: unsafe_token.unwrap_or(path.spans.start),
             end: group_span,
         },
         path,
@@ -322,6 +428,7 @@ where
     let input = &mut ParseBuffer::with_span(group.stream(), group.span());
 
     let mut fields = Vec::<FieldPat>::new();
+    let mut errors = ErrorAccumulator::new();
     let mut i = 0;
 
     while !input.is_empty() {
@@ -356,49 +463,89 @@ where
             let end = input.span();
             let spans = Spans { start, end };
 
-            return if let EmptyTrailing::Yes = trailing {
-                Ok((fields, Some(spans)))
+            if let EmptyTrailing::Yes = trailing {
+                errors.into_result()?;
+                return Ok((fields, Some(spans)));
             } else {
-                Err(Error::new(
+                errors.push(Error::new(
                     spans,
                     "only trailing `..` patterns are supported in structs",
-                ))
-            };
+                ));
+                input.skip_past_comma();
+                continue;
+            }
         }
 
         let mut attrs = attrs.clone();
         attrs.append(Attributes::parse(input));
 
-        let (name, pat_ident) = field_name_parser(i, input)?;
-        let pattern = Pattern::parse_inner(input, state, attrs)?;
-
-        let type_annotation = if matches!(
-            input.peek(),
-            Some(TokenTree::Punct(p))
-            if p.as_char() == ':'
-        ) {
-            let _ = input.parse_punct(':');
-            let type_ = ParsedType::parse(input)?;
-            Some(crate::pattern_processing::real_type_from(&pattern, type_)?)
-        } else {
-            None
-        };
+        let field = parse_struct_field(i, input, state, attrs, &mut field_name_parser)
+            .and_then(|field| input.parse_opt_punct(',').map(|_| field));
 
-        fields.push(FieldPat {
-            pat_ident,
-            name,
-            pattern,
-            type_annotation,
-        });
+        match field {
+            Ok(field) => fields.push(field),
+            Err(e) => {
+                // continues parsing after the next field separator
+                errors.push(e);
+                input.skip_past_comma();
+            }
+        }
 
         i += 1;
-
-        input.parse_opt_punct(',')?;
     }
 
+    errors.into_result()?;
+
     Ok((fields, None))
 }
 
+fn parse_struct_field<F>(
+    i: usize,
+    input: ParseStream<'_>,
+    state: &mut ParseState,
+    attrs: Attributes,
+    field_name_parser: &mut F,
+) -> Result<FieldPat, Error>
+where
+    F: FnMut(usize, ParseStream<'_>) -> Result<(FieldName, TokenTree), Error>,
+{
+    let (name, pat_ident) = field_name_parser(i, input)?;
+    let pattern = Pattern::parse_inner(input, state, attrs)?;
+
+    let accessor = match &pat_ident {
+        TokenTree::Literal(lit) if lit.to_string().starts_with('"') => {
+            if !matches!(pattern, Pattern::Ident(_) | Pattern::Underscore(_)) {
+                return Err(Error::new(
+                    pattern.spans(),
+                    "fields named with string literals can only be bound to constants or `_`",
+                ));
+            }
+            Some(name.accessor_ident(lit.span()))
+        }
+        _ => None,
+    };
+
+    let type_annotation = if matches!(
+        input.peek(),
+        Some(TokenTree::Punct(p))
+        if p.as_char() == ':'
+    ) {
+        let _ = input.parse_punct(':');
+        let type_ = ParsedType::parse(input)?;
+        Some(crate::pattern_processing::real_type_from(&pattern, type_)?)
+    } else {
+        None
+    };
+
+    Ok(FieldPat {
+        pat_ident,
+        name,
+        accessor,
+        pattern,
+        type_annotation,
+    })
+}
+
 struct Sequence {
     elems: Vec<Pattern>,
     rem: Option<usize>,
@@ -415,29 +562,52 @@ fn parse_sequence(
     let mut rem = None::<usize>;
     let mut i = 0usize;
     let mut comma_sep = false;
+    let mut errors = ErrorAccumulator::new();
 
     while !input.is_empty() {
-        let elem = Pattern::parse_inner(input, state, Attributes::new())?;
+        let elem = Pattern::parse_inner(input, state, Attributes::new()).and_then(|mut elem| {
+            if matches!(input.peek(), Some(tt) if tt.is_punct(':')) {
+                parse_coercion(input, &mut elem)?;
+            }
+            Ok(elem)
+        });
+
+        let elem = match elem {
+            Ok(elem) => elem,
+            Err(e) => {
+                // continues parsing after the next element separator
+                errors.push(e);
+                input.skip_past_comma();
+                i += 1;
+                continue;
+            }
+        };
 
         if let Pattern::Rem(rempat) = &elem {
             if let Some(_) = rem {
-                return Err(Error::new(
+                errors.push(Error::new(
                     elem.spans(),
                     format!("cannot use `..` multiple times in {}", type_constr),
                 ));
+            } else if errors.handle(rem_checker(rempat)).is_some() {
+                rem = Some(i);
             }
-
-            rem_checker(rempat)?;
-
-            rem = Some(i);
         }
 
         elems.push(elem);
 
-        comma_sep = comma_sep | input.parse_opt_punct(',')?.is_some();
+        match input.parse_opt_punct(',') {
+            Ok(comma) => comma_sep = comma_sep | comma.is_some(),
+            Err(e) => {
+                errors.push(e);
+                input.skip_past_comma();
+            }
+        }
         i += 1;
     }
 
+    errors.into_result()?;
+
     Ok(Sequence {
         elems,
         rem,
@@ -445,6 +615,56 @@ fn parse_sequence(
     })
 }
 
+/// Parses the `: &Type` coercion annotation after a binding in an array or tuple pattern
+fn parse_coercion(input: ParseStream<'_>, elem: &mut Pattern) -> Result<(), Error> {
+    let colon = input.parse_punct(':')?;
+
+    let binding = match elem {
+        Pattern::Ident(binding)
+        | Pattern::Rem(RemPat {
+            binding: Some(binding),
+            ..
+        }) => binding,
+        _ => {
+            return Err(Error::with_span(
+                colon.span(),
+                "coercion annotations can only be used on bindings",
+            ))
+        }
+    };
+
+    let ampersand = match input.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '&' => p.span(),
+        Some(tt) => {
+            return Err(Error::with_span(
+                tt.span(),
+                "expected a `&Type` coercion annotation",
+            ))
+        }
+        None => return Err(input.error("expected a `&Type` coercion annotation after this")),
+    };
+
+    // skips the lifetime, the generated constant always has a `'static` reference
+    if matches!(input.peek(), Some(tt) if tt.is_punct('\'')) {
+        input.next();
+        input.parse_ident()?;
+    }
+
+    let type_ = input.parse_opaque_type()?;
+    let is_str = matches!(
+        &type_.ty.clone().into_iter().collect::<Vec<_>>()[..],
+        [TokenTree::Ident(ident)] if ident.is_ident("str")
+    );
+
+    binding.coercion = Some(Coercion {
+        ampersand,
+        type_,
+        is_str,
+    });
+
+    Ok(())
+}
+
 fn parse_array(group: &Group, state: &mut ParseState) -> Result<ArrayPat, Error> {
     let brackets = group.span();
     let Sequence { elems, rem, .. } = parse_sequence(
@@ -512,6 +732,7 @@ fn make_binding(
         attrs,
         local: Ident::new(as_string, Span::mixed_site()).with_span(ident.span()),
         constant: ident.clone(),
+        coercion: None,
     }
 }
 
@@ -545,13 +766,21 @@ impl Pattern {
     pub(crate) fn is_not_rem(&self) -> bool {
         !matches!(self, Pattern::Rem { .. })
     }
-
-    pub(crate) fn to_token_stream(&self, ts: &mut TokenStream) {
+    /// Outputs this as a Rust pattern.
+    ///
+    /// Union patterns and struct patterns with fields named by string literals
+    /// are output as local variables, which are added to `accessed`
+    /// along with the struct pattern, so that they're destructured afterwards.
+    pub(crate) fn to_token_stream<'a>(
+        &'a self,
+        ts: &mut TokenStream,
+        accessed: &mut Vec<(&'a StructPat, Ident)>,
+    ) {
         match self {
             Pattern::Array(arr_pat) => {
                 tokenize_delim(Delimiter::Bracket, arr_pat.brackets, ts, |ts| {
                     for elem in &arr_pat.elems {
-                        elem.to_token_stream(ts);
+                        elem.to_token_stream(ts, accessed);
                         syntax::tokenize_comma(elem.end_span(), ts);
                     }
                 });
@@ -559,36 +788,18 @@ impl Pattern {
             Pattern::Tuple(tup_pat) => {
                 tokenize_delim(Delimiter::Parenthesis, tup_pat.parentheses, ts, |ts| {
                     for elem in &tup_pat.elems {
-                        elem.to_token_stream(ts);
+                        elem.to_token_stream(ts, accessed);
                         syntax::tokenize_comma(elem.end_span(), ts);
                     }
                 });
             }
-            Pattern::Struct(StructPat {
-                path,
-                group_span,
-                elems,
-                rem,
-                ..
-            }) => {
-                ts.extend(path.tokens.clone());
-                tokenize_delim(Delimiter::Brace, *group_span, ts, |ts| {
-                    for FieldPat {
-                        pat_ident, pattern, ..
-                    } in elems
-                    {
-                        ts.append_one(pat_ident.clone());
-                        ts.append_one(Punct::new(':', Spacing::Alone).with_span(pat_ident.span()));
-                        pattern.to_token_stream(ts);
-                        syntax::tokenize_comma(pattern.end_span(), ts);
-                    }
-
-                    if let Some(Spans { start, end }) = *rem {
-                        ts.append_one(Punct::new('.', Spacing::Joint).with_span(start));
-                        ts.append_one(Punct::new('.', Spacing::Alone).with_span(end));
-                    }
-                })
+            Pattern::Struct(struct_pat) if struct_pat.is_destructured_after() => {
+                let name = format!("__multiconst_struct{}", accessed.len());
+                let local = Ident::new(&name, Span::mixed_site()).with_span(struct_pat.group_span);
+                ts.append_one(local.clone());
+                accessed.push((struct_pat, local));
             }
+            Pattern::Struct(struct_pat) => struct_pat.to_token_stream(ts, accessed),
             Pattern::Rem(rem) => {
                 let end_span = rem.spans.end;
                 if let Some(Binding { local, .. }) = &rem.binding {
@@ -604,3 +815,46 @@ impl Pattern {
         }
     }
 }
+
+impl StructPat {
+    /// Whether the pattern has fields named with string literals
+    pub(crate) fn has_accessed_fields(&self) -> bool {
+        self.elems.iter().any(|e| e.accessor.is_some())
+    }
+
+    /// Whether the pattern is destructured after the pattern that contains it,
+    /// because it's a union pattern, whose field is read in its own `unsafe` block,
+    /// or because it has fields named with string literals.
+    pub(crate) fn is_destructured_after(&self) -> bool {
+        self.unsafe_token.is_some() || self.has_accessed_fields()
+    }
+
+    /// Outputs this as a Rust struct pattern,
+    /// leaving out the fields named with string literals.
+    pub(crate) fn to_token_stream<'a>(
+        &'a self,
+        ts: &mut TokenStream,
+        accessed: &mut Vec<(&'a StructPat, Ident)>,
+    ) {
+        ts.extend(self.path.tokens.clone());
+        tokenize_delim(Delimiter::Brace, self.group_span, ts, |ts| {
+            for FieldPat {
+                pat_ident, pattern, ..
+            } in self.elems.iter().filter(|e| e.accessor.is_none())
+            {
+                ts.append_one(pat_ident.clone());
+                ts.append_one(Punct::new(':', Spacing::Alone).with_span(pat_ident.span()));
+                pattern.to_token_stream(ts, accessed);
+                syntax::tokenize_comma(pattern.end_span(), ts);
+            }
+
+            let rem = match self.rem {
+                Some(rem) => rem,
+                None if self.has_accessed_fields() => Spans::from_one(self.group_span),
+                None => return,
+            };
+            ts.append_one(Punct::new('.', Spacing::Joint).with_span(rem.start));
+            ts.append_one(Punct::new('.', Spacing::Alone).with_span(rem.end));
+        })
+    }
+}
@@ -0,0 +1,742 @@
+use used_proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, TokenStream, TokenTree};
+
+use core::marker::PhantomData;
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    pattern::{ArrayPat, Binding, BindingAndType, Pattern, RemPat, StructPat, TuplePat},
+    syntax::{self, Crate, FieldMarkers, FieldName, OpaqueType, Spans},
+    type_::{ArrayType, ParsedType, RealType, SliceType, TupleType, Type},
+    utils::{ident_to_string_no_raw, TokenStreamExt, WithSpan},
+    Error,
+};
+
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+#[derive(Copy, Clone)]
+pub(crate) enum FieldType<'a> {
+    Direct(&'a RealType),
+    Derived {
+        spans: Spans,
+        field_name: &'a FieldName,
+        inside: &'a FieldType<'a>,
+    },
+}
+
+impl<'a> FieldType<'a> {
+    fn spans(self) -> Spans {
+        match self {
+            FieldType::Direct(x) => x.spans(),
+            FieldType::Derived { spans, .. } => spans,
+        }
+    }
+    fn to_opaque(self, pctx: &mut ExtractConstCtx<'_>) -> OpaqueType {
+        let mut ty = TokenStream::new();
+        let spans = self.to_token_stream(pctx, &mut ty);
+        OpaqueType { ty, spans }
+    }
+
+    fn to_tokens(self, pctx: &mut ExtractConstCtx<'_>) -> TokenStream {
+        let mut ts = TokenStream::new();
+        self.to_token_stream(pctx, &mut ts);
+        ts
+    }
+
+    fn to_token_stream(self, pctx: &mut ExtractConstCtx<'_>, ts: &mut TokenStream) -> Spans {
+        let crate_kw = pctx.crate_kw;
+        match self {
+            FieldType::Direct(x) => {
+                x.to_token_stream(ts);
+                x.spans()
+            }
+            FieldType::Derived { spans, .. } => {
+                let mut path = Vec::new();
+                let root = self.root_and_path(&mut path);
+
+                crate_kw.item_to_ts("GetFieldType", spans, ts);
+                ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+                ts.extend(root.to_tokens());
+                ts.append_one(Punct::new(',', Spacing::Alone).with_span(root.end_span()));
+                let markers = pctx.field_markers.as_deref_mut();
+                FieldName::path_to_token_stream(path, crate_kw, markers, spans, ts);
+                ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+
+                spans
+            }
+        }
+    }
+
+    // outputs the `ASSERT_EXISTS` constant of the `FieldType` impl for this field,
+    // which errors if the field doesn't exist.
+    fn assert_exists_to_tokens(self, pctx: &mut ExtractConstCtx<'_>) -> TokenStream {
+        let crate_kw = pctx.crate_kw;
+        let spans = self.spans();
+        let mut path = Vec::new();
+        let root = self.root_and_path(&mut path);
+        let mut ts = TokenStream::new();
+
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+        ts.extend(root.to_tokens());
+        ts.append_keyword("as", spans.start);
+        crate_kw.item_to_ts("FieldType", spans, &mut ts);
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+        let markers = pctx.field_markers.as_deref_mut();
+        FieldName::path_to_token_stream(path, crate_kw, markers, spans, &mut ts);
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Punct::new(':', Spacing::Joint).with_span(spans.end));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Ident::new("ASSERT_EXISTS", spans.end));
+        ts
+    }
+
+    // outputs the `EXISTS` constant of the `FieldAccessor` impl for the `field_name` field
+    // of this type, which errors if the type doesn't have a method to read that field.
+    fn assert_accessor_to_tokens(
+        self,
+        field_name: &FieldName,
+        pctx: &mut ExtractConstCtx<'_>,
+    ) -> TokenStream {
+        let crate_kw = pctx.crate_kw;
+        let spans = field_name.spans();
+        let mut ts = TokenStream::new();
+
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+        // so that the error points at the field name
+        ts.extend(self.to_tokens(pctx).with_span(spans.start));
+        ts.append_keyword("as", spans.start);
+        crate_kw.item_to_ts("FieldAccessor", spans, &mut ts);
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+        match pctx.field_markers.as_deref_mut() {
+            Some(markers) => markers.name_to_token_stream(field_name, crate_kw, &mut ts),
+            None => field_name.to_token_stream(crate_kw, &mut ts),
+        }
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Punct::new(':', Spacing::Joint).with_span(spans.end));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(spans.end));
+        ts.append_one(Ident::new("EXISTS", spans.end));
+        ts
+    }
+
+    // returns the type that the field path starts from,
+    // and collects the field names of the path into `path`.
+    fn root_and_path(self, path: &mut Vec<&'a FieldName>) -> &'a RealType {
+        match self {
+            FieldType::Direct(x) => x,
+            FieldType::Derived {
+                field_name, inside, ..
+            } => {
+                let root = inside.root_and_path(path);
+                path.push(field_name);
+                root
+            }
+        }
+    }
+}
+
+pub(crate) struct ExtractConstCtx<'a> {
+    pub(crate) bats: &'a mut Vec<BindingAndType>,
+    pub(crate) checked_locals: &'a mut Vec<CheckedLocal>,
+    /// The length of the `..` pattern in tuple patterns
+    pub(crate) tuple_rem_lens: &'a mut Vec<TokenStream>,
+    pub(crate) tuple_rem_pat_const: &'a Ident,
+    /// The `ASSERT_EXISTS` constants for the last element that
+    /// array patterns require of arrays whose type isn't written as an array,
+    /// and the `FieldAccessor::EXISTS` constants for fields named by string literals.
+    pub(crate) elem_assertions: &'a mut Vec<TokenStream>,
+    pub(crate) crate_kw: &'a Crate,
+    /// The marker types for the names of queried fields,
+    /// `None` where the marker types can't be declared.
+    pub(crate) field_markers: Option<&'a mut FieldMarkers>,
+    /// The path to the pattern that's being processed, eg: `.foo[3]`
+    pub(crate) field_path: String,
+    /// The position of the innermost array or tuple element that's being processed,
+    /// see `BindingAndType::element_index`.
+    pub(crate) element_index: Option<String>,
+    /// Whether the pattern that's being processed is inside a slice pattern,
+    /// which binds references to the values.
+    pub(crate) in_slice: bool,
+    /// Whether the pattern is refutable, because it matches slices of some lengths.
+    pub(crate) is_refutable: bool,
+}
+
+impl ExtractConstCtx<'_> {
+    fn push_binding(&mut self, mut bat: BindingAndType) -> Result<(), Error> {
+        check_reserved_name(&bat.constant)?;
+
+        // constants with cfgs can have the same name as other constants,
+        // so long as they're not enabled at the same time.
+        if !bat.attrs.is_conditional() {
+            let declared = self.bats.iter().filter(|b| !b.attrs.is_conditional());
+            check_not_declared(&bat.constant, declared.map(|b| &b.constant))?;
+        }
+
+        bat.field_path = self.field_path.clone();
+        bat.element_index = self.element_index.clone();
+        bat.by_ref = self.in_slice;
+        self.bats.push(bat);
+        Ok(())
+    }
+
+    /// Runs `f` with `self.in_slice` set to `in_slice`
+    fn with_in_slice<F, T>(&mut self, in_slice: bool, f: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let prev = core::mem::replace(&mut self.in_slice, in_slice);
+        let ret = f(self);
+        self.in_slice = prev;
+        ret
+    }
+
+    /// Runs `f` with `segment` appended to `self.field_path`
+    fn with_path_segment<F, T>(&mut self, segment: &str, f: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let len = self.field_path.len();
+        self.field_path.push_str(segment);
+        let ret = f(self);
+        self.field_path.truncate(len);
+        ret
+    }
+
+    /// Runs `f` with `segment` appended to `self.field_path`,
+    /// and `index` as the position of the element that's being processed.
+    fn with_element<F, T>(&mut self, segment: &str, index: String, f: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let prev = self.element_index.replace(index);
+        let ret = self.with_path_segment(segment, f);
+        self.element_index = prev;
+        ret
+    }
+}
+
+/// The prefix of the names of the hidden constants that the macros generate.
+pub(crate) const HIDDEN_CONST_PREFIX: &str = "__PRIV_MULTICONST_";
+
+/// The name of a hidden constant, `__PRIV_MULTICONST_{kind}__{ident}`,
+/// or `__PRIV_MULTICONST__{ident}` if `kind` is empty.
+///
+/// `kind` must be uppercase letters, so that the names of hidden constants of
+/// different kinds can't collide.
+pub(crate) fn hidden_const_name(kind: &str, ident: &Ident) -> String {
+    let separator = if kind.is_empty() { "_" } else { "__" };
+    format!(
+        "{}{}{}{}",
+        HIDDEN_CONST_PREFIX,
+        kind,
+        separator,
+        ident_to_string_no_raw(ident)
+    )
+}
+
+/// Errors if `name` could collide with the hidden constants that the macros generate.
+pub(crate) fn check_reserved_name(name: &Ident) -> Result<(), Error> {
+    if ident_to_string_no_raw(name).starts_with(HIDDEN_CONST_PREFIX) {
+        Err(Error::with_span(
+            name.span(),
+            format!(
+                "names starting with `{}` are reserved for the generated constants",
+                HIDDEN_CONST_PREFIX,
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Errors if a constant named `name` is in `declared`, pointing at both constants.
+pub(crate) fn check_not_declared<'a, I>(name: &Ident, declared: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = &'a Ident>,
+{
+    let name_str = ident_to_string_no_raw(name);
+
+    match declared
+        .into_iter()
+        .find(|x| ident_to_string_no_raw(x) == name_str)
+    {
+        Some(prev) => Err(Error::with_span(
+            name.span(),
+            format!("the `{}` constant is declared multiple times", name_str),
+        )
+        .join(Error::with_span(
+            prev.span(),
+            format!("`{}` is first declared here", name_str),
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub(crate) struct CheckedLocal {
+    pub(crate) binding: Ident,
+    pub(crate) type_: OpaqueType,
+    /// Whether the binding is a reference to the value, see `BindingAndType::by_ref`.
+    pub(crate) by_ref: bool,
+}
+
+/// Whether a pattern is the whole pattern for a struct field.
+#[derive(Copy, Clone)]
+pub(crate) enum WholeFieldPat {
+    Yes,
+    No,
+}
+
+/// Finds the first constant in the pattern whose binding matches `pred`.
+pub(crate) fn find_first_const_ident<'a>(
+    pattern: &'a Pattern,
+    pred: &dyn Fn(&Binding) -> bool,
+) -> Option<&'a Ident> {
+    let rec = |pattern: &'a Pattern| find_first_const_ident(pattern, pred);
+    match pattern {
+        Pattern::Array(ArrayPat { elems, .. }) | Pattern::Tuple(TuplePat { elems, .. }) => {
+            elems.iter().find_map(rec)
+        }
+        Pattern::Struct(StructPat { elems, .. }) => elems.iter().find_map(|fp| rec(&fp.pattern)),
+        Pattern::Underscore(_) => None,
+        Pattern::Rem(RemPat { binding, .. }) => {
+            binding.as_ref().filter(|b| pred(b)).map(|b| &b.constant)
+        }
+        Pattern::Ident(binding) => Some(&binding.constant).filter(|_| pred(binding)),
+    }
+}
+
+/// Finds the first element pattern in the array pattern that can infer its own length.
+fn find_first_ok_real_type(elems: &[Pattern], type_: ParsedType) -> Result<RealType, Error> {
+    if elems.is_empty() {
+        return type_.definite_length_real_type();
+    }
+
+    let mut last_res = None;
+
+    for elem in elems.iter().filter(|p| p.is_not_rem()) {
+        match real_type_from(elem, type_.clone()) {
+            Ok(x) => return Ok(x),
+            Err(e) => last_res = Some(e),
+        }
+    }
+
+    Err(last_res.unwrap())
+}
+
+pub(crate) fn real_type_from(pattern: &Pattern, type_: ParsedType) -> Result<RealType, Error> {
+    match (pattern, type_) {
+        (Pattern::Underscore { .. }, ty)
+        | (Pattern::Ident { .. }, ty)
+        | (Pattern::Struct { .. }, ty) => ty.definite_length_real_type(),
+        (Pattern::Array(ArrayPat { rem, elems, .. }), Type::Array(arr_ty)) => {
+            let len = match (rem, arr_ty.len) {
+                (_, Some(len)) => len,
+                (Some(_), None) => {
+                    let msg = "cannot infer length because the pattern contains a `..`";
+                    return Err(Error::with_span(arr_ty.brackets, msg));
+                }
+                (None, None) => {
+                    let x = Literal::usize_unsuffixed(elems.len()).with_span(arr_ty.brackets);
+                    let x = TokenTree::Literal(x);
+                    TokenStream::from(x)
+                }
+            };
+
+            Ok(Type::Array(ArrayType {
+                brackets: arr_ty.brackets,
+                elem_ty: Box::new(find_first_ok_real_type(elems, *arr_ty.elem_ty)?),
+                len,
+            }))
+        }
+        (Pattern::Array(ArrayPat { elems, .. }), Type::Slice(slice_ty)) => {
+            Ok(Type::Slice(SliceType {
+                ampersand: slice_ty.ampersand,
+                lifetime: slice_ty.lifetime,
+                brackets: slice_ty.brackets,
+                elem_ty: Box::new(find_first_ok_real_type(elems, *slice_ty.elem_ty)?),
+            }))
+        }
+        (Pattern::Tuple(tup_pat), Type::Tuple(tup_ty)) => {
+            let mut elem_tys = Vec::new();
+
+            {
+                let check = if tup_pat.rem.is_none() {
+                    tup_pat.elems.len() != tup_ty.elem_tys.len()
+                } else {
+                    tup_pat.elems.len() - 1 > tup_ty.elem_tys.len()
+                };
+
+                if check {
+                    let msg = alloc::format!(
+                        "tuple pattern has {} elements, but type has {}",
+                        tup_pat.elems.len(),
+                        tup_ty.elem_tys.len(),
+                    );
+                    return Err(Error::with_span(tup_pat.parentheses, msg));
+                }
+            }
+
+            let (before_elems, taken, skipped, after_elems) = if let Some(pos) = tup_pat.rem {
+                let skipped = tup_ty
+                    .elem_tys
+                    .len()
+                    .saturating_sub(tup_pat.elems.len() - 1);
+                (
+                    &tup_pat.elems[..pos],
+                    pos,
+                    skipped,
+                    &tup_pat.elems[pos + 1..],
+                )
+            } else {
+                (&tup_pat.elems[..], tup_ty.elem_tys.len(), 0, &[][..])
+            };
+
+            let mut tys_iter = tup_ty.elem_tys.into_iter();
+
+            for (elem, elem_ty) in before_elems.iter().zip(tys_iter.by_ref().take(taken)) {
+                elem_tys.push(real_type_from(elem, elem_ty)?);
+            }
+            for elem_ty in tys_iter.by_ref().take(skipped) {
+                elem_tys.push(elem_ty.definite_length_real_type()?);
+            }
+            for (elem, elem_ty) in after_elems.iter().zip(tys_iter.by_ref()) {
+                elem_tys.push(real_type_from(elem, elem_ty)?);
+            }
+
+            Ok(Type::Tuple(TupleType {
+                parentheses: tup_ty.parentheses,
+                elem_tys,
+                _marker: PhantomData,
+            }))
+        }
+        (_, type_ @ Type::Opaque { .. }) | (_, type_ @ Type::Underscore { .. }) => {
+            type_.definite_length_real_type()
+        }
+        (pat, Type::Array { .. }) | (pat, Type::Slice { .. }) | (pat, Type::Tuple { .. }) => {
+            let s = "mismatched pattern and type";
+            Err(Error::new(pat.spans(), s))
+        }
+    }
+}
+
+pub(crate) fn extract_const_names_tys(
+    pattern: &Pattern,
+    type_: FieldType<'_>,
+    in_struct: WholeFieldPat,
+    pctx: &mut ExtractConstCtx<'_>,
+) -> Result<(), Error> {
+    match pattern {
+        Pattern::Ident(pat_ident) => {
+            let type_ = type_.to_opaque(pctx);
+
+            pctx.push_binding(pat_ident.with_type(type_))
+        }
+        Pattern::Underscore(b) => {
+            // Only ignore the type when it's an ignored struct field that
+            // doesn't have a type annotation.
+            //
+            // `_` patterns nested in other patterns do assert the type though.
+            let type_ = match (type_, in_struct) {
+                (FieldType::Direct(ty), _) => ty.to_opaque(),
+                (FieldType::Derived { .. }, WholeFieldPat::No) => type_.to_opaque(pctx),
+                (FieldType::Derived { .. }, WholeFieldPat::Yes) => return Ok(()),
+            };
+
+            pctx.checked_locals.push(CheckedLocal {
+                binding: b.local.clone(),
+                type_,
+                by_ref: pctx.in_slice,
+            });
+
+            Ok(())
+        }
+        Pattern::Struct(struct_pat) => {
+            for elem in &struct_pat.elems {
+                let subfield_ty = match &elem.type_annotation {
+                    Some(x) => FieldType::Direct(x),
+                    None => FieldType::Derived {
+                        spans: elem.name.spans(),
+                        field_name: &elem.name,
+                        inside: &type_,
+                    },
+                };
+
+                // errors with a clear message if the struct can't read fields by that name
+                if elem.accessor.is_some() {
+                    let assertion = type_.assert_accessor_to_tokens(&elem.name, pctx);
+                    pctx.elem_assertions.push(assertion);
+                }
+
+                let segment = match &elem.name {
+                    FieldName::Numeric(n, _) => format!(".{}", n),
+                    FieldName::Alphabetic(name, _) => format!(".{}", name),
+                    FieldName::NumericConst { .. } => unreachable!("not used in struct patterns"),
+                };
+
+                // union fields and fields named by string literals are read by value,
+                // even inside slices
+                let in_slice =
+                    pctx.in_slice && elem.accessor.is_none() && struct_pat.unsafe_token.is_none();
+
+                pctx.with_path_segment(&segment, |pctx| {
+                    pctx.with_in_slice(in_slice, |pctx| {
+                        extract_const_names_tys(
+                            &elem.pattern,
+                            subfield_ty,
+                            WholeFieldPat::Yes,
+                            pctx,
+                        )
+                    })
+                })?;
+            }
+            Ok(())
+        }
+        Pattern::Array(arr_pat) => process_arr_pat(arr_pat, type_, pctx),
+        Pattern::Tuple(tup_pat) => process_tup_pat(tup_pat, type_, pctx),
+        Pattern::Rem { .. } => unreachable!("{}", core::panic::Location::caller()),
+    }
+}
+
+fn process_arr_pat(
+    arr_pat: &ArrayPat,
+    type_: FieldType<'_>,
+    pctx: &mut ExtractConstCtx<'_>,
+) -> Result<(), Error> {
+    let ExtractConstCtx { crate_kw, .. } = *pctx;
+
+    let spans = Spans::from_one(arr_pat.brackets);
+    let field_name;
+    let subfield_ty = match type_ {
+        FieldType::Direct(Type::Array(ArrayType { elem_ty, .. }))
+        | FieldType::Direct(Type::Slice(SliceType { elem_ty, .. })) => FieldType::Direct(elem_ty),
+        FieldType::Direct(Type::Opaque { .. }) | FieldType::Derived { .. } => {
+            // errors with a clear message if the array is shorter than the pattern,
+            // by asserting that the last element that the pattern requires exists.
+            let required = arr_pat.elems.len() - arr_pat.rem.map_or(0, |_| 1);
+            if let Some(last) = required.checked_sub(1) {
+                let last_name = FieldName::Numeric(last, spans);
+                let last_ty = FieldType::Derived {
+                    spans,
+                    field_name: &last_name,
+                    inside: &type_,
+                };
+                let assertion = last_ty.assert_exists_to_tokens(pctx);
+                pctx.elem_assertions.push(assertion);
+            }
+
+            field_name = FieldName::Numeric(0, spans);
+            FieldType::Derived {
+                spans,
+                field_name: &field_name,
+                inside: &type_,
+            }
+        }
+        FieldType::Direct(ty) => {
+            return Err(Error::new(ty.spans(), "expected array type"));
+        }
+    };
+
+    // slice patterns bind references to the elements
+    let is_slice = matches!(type_, FieldType::Direct(Type::Slice { .. }));
+    if is_slice && (arr_pat.rem.is_none() || arr_pat.elems.len() > 1) {
+        pctx.is_refutable = true;
+    }
+
+    let rem_length = |pctx: &mut ExtractConstCtx<'_>| {
+        let count = arr_pat.elems.len() - 1; // 1 being the remainder pattern
+        let count = Literal::usize_unsuffixed(count).with_span(arr_pat.brackets);
+
+        match type_ {
+            FieldType::Direct(Type::Array(ArrayType { len, .. })) => {
+                let mut ts = len.clone();
+                ts.append_one(Punct::new('-', Spacing::Alone).with_span(arr_pat.brackets));
+                ts.append_one(count);
+                ts
+            }
+            // doesn't error with a subtraction overflow for arrays shorter than the pattern,
+            // which the assertion in `elem_assertions` errors for.
+            FieldType::Direct(_) | FieldType::Derived { .. } => {
+                let spans = type_.spans();
+                let mut args =
+                    syntax::tokenize_seq_length_assoc_const(crate_kw, spans, type_.to_tokens(pctx));
+                syntax::tokenize_comma(arr_pat.brackets, &mut args);
+                args.append_one(count);
+
+                let mut ts = TokenStream::new();
+                crate_kw.item_to_ts("array_rem_length", spans, &mut ts);
+                ts.append_one(Group::new(Delimiter::Parenthesis, args).with_span(spans.end));
+                ts
+            }
+        }
+    };
+
+    let rem_pos = arr_pat.rem.unwrap_or(arr_pat.elems.len());
+    let elem_count = arr_pat.elems.len();
+    let array_len = match type_ {
+        FieldType::Direct(Type::Array(ArrayType { len, .. })) => len.to_string().parse().ok(),
+        _ => None,
+    };
+
+    for (i, elem) in arr_pat.elems.iter().enumerate() {
+        match elem {
+            Pattern::Rem(RemPat {
+                binding: Some(binding),
+                ..
+            }) => {
+                // the remainder of a slice is a slice reference,
+                // regardless of whether the slice is itself bound by reference.
+                let (elem_ty, in_slice) = if is_slice {
+                    (type_.to_opaque(pctx), false)
+                } else {
+                    let elem_ty = Type::Array(ArrayType {
+                        brackets: binding.constant.span(),
+                        elem_ty: Box::new(Type::Opaque(subfield_ty.to_opaque(pctx))),
+                        len: rem_length(pctx),
+                    })
+                    .to_opaque();
+                    (elem_ty, pctx.in_slice)
+                };
+
+                let trailing = elem_count - 1 - i;
+                let bat = binding.with_type(elem_ty);
+                let segment = &if trailing == 0 {
+                    format!("[{}..]", i)
+                } else {
+                    format!("[{}..len - {}]", i, trailing)
+                };
+                pctx.with_element(segment, i.to_string(), |pctx| {
+                    pctx.with_in_slice(in_slice, |pctx| pctx.push_binding(bat))
+                })?;
+            }
+            Pattern::Rem(_) => {}
+            _ => {
+                let (segment, index) = if i < rem_pos {
+                    (format!("[{}]", i), i.to_string())
+                } else {
+                    let from_end = elem_count - i;
+                    let index = index_from_end(array_len, from_end);
+                    (format!("[len - {}]", from_end), index)
+                };
+                let in_slice = is_slice || pctx.in_slice;
+                pctx.with_element(&segment, index, |pctx| {
+                    pctx.with_in_slice(in_slice, |pctx| {
+                        extract_const_names_tys(elem, subfield_ty, WholeFieldPat::No, pctx)
+                    })
+                })?
+            }
+        }
+    }
+    Ok(())
+}
+fn process_tup_pat(
+    tup_pat: &TuplePat,
+    type_: FieldType<'_>,
+    pctx: &mut ExtractConstCtx<'_>,
+) -> Result<(), Error> {
+    let ExtractConstCtx { crate_kw, .. } = *pctx;
+
+    let rem_pos = tup_pat.rem.unwrap_or_else(|| tup_pat.elems.len());
+    let trailing_pattern_count = tup_pat.elems.len() - rem_pos;
+
+    let mut i = 0;
+    for (pos, elem) in tup_pat.elems.iter().enumerate() {
+        let spans = elem.spans();
+        let field_name;
+        let subfield_ty = match type_ {
+            FieldType::Direct(Type::Tuple(TupleType { elem_tys, .. })) => {
+                if let Pattern::Rem(_) = elem {
+                    i += elem_tys.len().saturating_sub(tup_pat.elems.len() - 1);
+                    continue;
+                } else {
+                    let elem_ty = elem_tys.get(i).ok_or_else(|| {
+                        Error::new(elem.spans(), "tuple element does not exist in the type")
+                    })?;
+                    FieldType::Direct(elem_ty)
+                }
+            }
+            FieldType::Direct(Type::Opaque { .. }) | FieldType::Derived { .. } => {
+                if let Pattern::Rem(_) = elem {
+                    continue;
+                }
+                // elements after the `..` are indexed from the end of the tuple
+                field_name = if pos > rem_pos {
+                    let sspan = elem.spans().start;
+                    let mut trail_off = syntax::tokenize_seq_length_assoc_const(
+                        crate_kw,
+                        type_.spans(),
+                        type_.to_tokens(pctx),
+                    );
+
+                    trail_off.append_one(Punct::new('-', Spacing::Alone).with_span(sspan));
+                    trail_off.append_one(
+                        Literal::usize_unsuffixed(trailing_pattern_count).with_span(sspan),
+                    );
+
+                    let len_index = pctx.tuple_rem_lens.len();
+                    pctx.tuple_rem_lens.push(trail_off);
+
+                    let trailing_index = pos - rem_pos;
+                    let num_const = TokenStream::from_array([
+                        TokenTree::Ident(pctx.tuple_rem_pat_const.clone()),
+                        {
+                            let x = Literal::usize_unsuffixed(len_index).with_span(sspan);
+                            let x = TokenTree::Literal(x).with_span(sspan);
+                            let x = TokenStream::from(x).with_span(sspan);
+                            Group::new(Delimiter::Bracket, x).with_span(sspan).into()
+                        },
+                        Punct::new('+', Spacing::Alone).with_span(sspan).into(),
+                        Literal::usize_unsuffixed(trailing_index)
+                            .with_span(sspan)
+                            .into(),
+                    ]);
+
+                    FieldName::NumericConst(num_const, elem.spans())
+                } else {
+                    FieldName::Numeric(i, spans)
+                };
+                FieldType::Derived {
+                    spans,
+                    field_name: &field_name,
+                    inside: &type_,
+                }
+            }
+            FieldType::Direct(ty) => {
+                return Err(Error::new(ty.spans(), "expected tuple type"));
+            }
+        };
+
+        let process_elem = |pctx: &mut ExtractConstCtx<'_>| {
+            extract_const_names_tys(elem, subfield_ty, WholeFieldPat::No, pctx)
+        };
+
+        match type_ {
+            FieldType::Direct(Type::Tuple { .. }) => {
+                pctx.with_element(&format!(".{}", i), i.to_string(), process_elem)?
+            }
+            _ if pos < rem_pos => {
+                pctx.with_element(&format!(".{}", i), i.to_string(), process_elem)?
+            }
+            _ => {
+                let from_end = tup_pat.elems.len() - pos;
+                let segment = &format!(".(len - {})", from_end);
+                pctx.with_element(segment, index_from_end(None, from_end), process_elem)?
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+// The position of an element after a `..` pattern,
+// which can only be computed when the length of the sequence is known.
+fn index_from_end(len: Option<usize>, from_end: usize) -> String {
+    match len.and_then(|len| len.checked_sub(from_end)) {
+        Some(index) => index.to_string(),
+        None => format!("len - {}", from_end),
+    }
+}
@@ -0,0 +1,748 @@
+//! Syntax-related types and functions, no extension traits.
+
+use used_proc_macro::{
+    Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+};
+
+use alloc::{format, rc::Rc, string::ToString, vec::Vec};
+
+use crate::{
+    parsing::ParseStream,
+    utils::{self, IsIdent, TokenStreamExt, TokenTreeExt, WithSpan},
+    Error,
+};
+
+#[cfg(test)]
+mod testing;
+
+///////////////////////////////////////////////////////////////////////////////
+
+enum PathToken {
+    Type,
+    Colon,
+    Other,
+}
+
+fn is_path_token(tt: Option<&TokenTree>) -> Option<PathToken> {
+    match tt {
+        Some(TokenTree::Ident(_)) => Some(PathToken::Other),
+        Some(TokenTree::Punct(p)) => match p.as_char() {
+            '<' => Some(PathToken::Type),
+            ':' => Some(PathToken::Colon),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Loose path parsing
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+pub(crate) struct Path {
+    pub(crate) tokens: TokenStream,
+    pub(crate) spans: Spans,
+}
+
+impl Path {
+    pub(crate) fn parse(input: ParseStream<'_>) -> Result<Path, Error> {
+        let mut out = TokenStream::new();
+        let start = input.span();
+        let mut prev_pt = PathToken::Other;
+
+        while let Some(pt) = is_path_token(input.peek()) {
+            match pt {
+                PathToken::Type => {
+                    if out.is_empty() {
+                        return Err(Error::with_span(input.span(), "path can't start with `<`"));
+                    }
+
+                    let ot = input.parse_opaque_type_with(|_| true)?;
+
+                    if !matches!(prev_pt, PathToken::Colon) {
+                        let c2span = ot.spans.start;
+                        out.append_one(Punct::new(':', Spacing::Joint).with_span(c2span));
+                        out.append_one(Punct::new(':', Spacing::Alone).with_span(c2span));
+                    }
+                    out.extend(ot.ty)
+                }
+                PathToken::Colon | PathToken::Other => {
+                    out.extend(input.next());
+                }
+            }
+            prev_pt = pt;
+        }
+
+        if out.is_empty() {
+            return Err(input.error("expected path after this token"));
+        }
+        let spans = Spans {
+            start,
+            end: input.span(),
+        };
+
+        Ok(Path { tokens: out, spans })
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The marker types for the identifiers of the fields that struct patterns query,
+/// which are named after those fields, so that the errors for nonexistent fields name them.
+///
+/// The marker types are declared in a hidden module, with `into_module_tokens`.
+pub(crate) struct FieldMarkers {
+    module: Ident,
+    /// The names of the marker types, and the `TIdent`s of the fields.
+    markers: Vec<(Rc<str>, TokenStream, Span)>,
+}
+
+impl FieldMarkers {
+    pub(crate) fn new(module: Ident) -> Self {
+        Self {
+            module,
+            markers: Vec::new(),
+        }
+    }
+
+    /// Outputs `TField<module::marker>` for identifiers that are valid type names,
+    /// and the type-level name of the field otherwise.
+    pub(crate) fn name_to_token_stream(
+        &mut self,
+        name: &FieldName,
+        crate_kw: &Crate,
+        ts: &mut TokenStream,
+    ) {
+        let (str, spans) = match name {
+            FieldName::Alphabetic(str, spans) if is_marker_name(str) => (str, *spans),
+            _ => return name.to_token_stream(crate_kw, ts),
+        };
+        let span = spans.start;
+
+        if !self.markers.iter().any(|(m, ..)| m == str) {
+            let mut name_ts = TokenStream::new();
+            name.to_token_stream(crate_kw, &mut name_ts);
+            self.markers.push((str.clone(), name_ts, span));
+        }
+
+        crate_kw.item_to_ts("TField", spans, ts);
+        ts.append_one(Punct::new('<', Spacing::Alone).with_span(span));
+        ts.append_one(self.module.clone());
+        ts.append_one(Punct::new(':', Spacing::Joint).with_span(span));
+        ts.append_one(Punct::new(':', Spacing::Alone).with_span(span));
+        ts.append_one(Ident::new_raw(str, span));
+        ts.append_one(Punct::new('>', Spacing::Alone).with_span(span));
+    }
+
+    /// Outputs the module that declares the marker types,
+    /// nothing if no marker types were used.
+    pub(crate) fn into_module_tokens(self, crate_kw: &Crate, attrs: TokenStream) -> TokenStream {
+        let FieldMarkers { module, markers } = self;
+        let mut ts = TokenStream::new();
+        if markers.is_empty() {
+            return ts;
+        }
+        let span = module.span();
+
+        ts.extend(attrs);
+        ts.extend(
+            "#[doc(hidden)] #[allow(non_camel_case_types)]"
+                .parse::<TokenStream>()
+                .unwrap()
+                .with_span(span),
+        );
+        ts.append_keyword("mod", span);
+        ts.append_one(module);
+        tokenize_delim(Delimiter::Brace, span, &mut ts, |ts| {
+            for (marker, name, mspan) in markers {
+                let marker = Ident::new_raw(&marker, mspan);
+
+                ts.append_keyword("pub", span);
+                ts.append_keyword("struct", span);
+                ts.append_one(marker.clone());
+                ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+
+                ts.append_keyword("impl", span);
+                crate_kw.item_to_ts("FieldMarker", Spans::from_one(span), ts);
+                ts.append_keyword("for", span);
+                ts.append_one(marker);
+                tokenize_delim(Delimiter::Brace, span, ts, |ts| {
+                    ts.append_keyword("type", span);
+                    ts.append_keyword("Name", span);
+                    ts.append_one(Punct::new('=', Spacing::Alone).with_span(span));
+                    ts.extend(name);
+                    ts.append_one(Punct::new(';', Spacing::Alone).with_span(span));
+                });
+            }
+        });
+        ts
+    }
+}
+
+// Whether `name` can be the name of a marker type, as a raw identifier
+fn is_marker_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let first_is_valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_');
+
+    first_is_valid
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(name, "_" | "crate" | "self" | "Self" | "super")
+}
+
+const EXPECTED_FIELD_NAME: &str = "expected either an untyped numeric literal or an identifier";
+
+const EXPECTED_STR_FIELD_NAME: &str =
+    "expected a string literal with a field name, eg: `\"foo\"`, `\"0\"`";
+
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+pub(crate) enum FieldName {
+    Numeric(usize, Spans),
+    Alphabetic(Rc<str>, Spans),
+    /// A numeric identifier, determined by a constant in the expanded code.
+    NumericConst(TokenStream, Spans),
+}
+
+impl FieldName {
+    pub(crate) fn spans(&self) -> Spans {
+        match *self {
+            FieldName::Numeric(_, spans)
+            | FieldName::Alphabetic(_, spans)
+            | FieldName::NumericConst(_, spans) => spans,
+        }
+    }
+
+    #[cfg(feature = "__derive")]
+    pub(crate) fn tokens(&self, crate_path: &syn::Path) -> TokenStream {
+        use quote::ToTokens;
+
+        let mut ts = TokenStream::new();
+        self.to_token_stream_inner(&mut ts, &|item_name, spans, ts| {
+            let item = Ident::new(item_name, spans.end);
+            ts.extend(quote::quote_spanned!(spans.start=> #crate_path::__::));
+            item.to_tokens(ts);
+        });
+        ts
+    }
+
+    fn to_token_stream_inner(
+        &self,
+        ts: &mut TokenStream,
+        item_to_ts: &dyn Fn(&str, Spans, &mut TokenStream),
+    ) {
+        match *self {
+            FieldName::Numeric(n, spans) => {
+                item_to_ts("Usize", spans, ts);
+
+                ts.append_one(Punct::new('<', Spacing::Joint).with_span(spans.start));
+                ts.append_one(Literal::usize_unsuffixed(n).with_span(spans.end));
+                ts.append_one(Punct::new('>', Spacing::Joint).with_span(spans.end));
+            }
+            FieldName::Alphabetic(ref str, spans) => {
+                let mut chars = str.chars();
+                let span = spans.start;
+
+                item_to_ts("TIdent", spans, ts);
+
+                ts.append_one(Punct::new('<', Spacing::Joint).with_span(span));
+
+                tokenize_delim(Delimiter::Parenthesis, span, ts, |ts| {
+                    while !chars.as_str().is_empty() {
+                        item_to_ts("TChars", spans, ts);
+
+                        ts.append_one(Punct::new('<', Spacing::Joint).with_span(span));
+
+                        chars
+                            .by_ref()
+                            .chain(core::iter::repeat(' '))
+                            .take(8)
+                            .for_each(|c| {
+                                ts.append_one(Literal::character(c).with_span(span));
+                                ts.append_one(Punct::new(',', Spacing::Joint).with_span(span));
+                            });
+
+                        ts.append_one(Punct::new('>', Spacing::Joint).with_span(span));
+                        ts.append_one(Punct::new(',', Spacing::Joint).with_span(span));
+                    }
+                });
+
+                ts.append_one(Punct::new('>', Spacing::Joint).with_span(span));
+            }
+            FieldName::NumericConst(ref x, spans) => {
+                item_to_ts("Usize", spans, ts);
+
+                ts.append_one(Punct::new('<', Spacing::Joint).with_span(spans.start));
+                ts.append_one(Group::new(Delimiter::Brace, x.clone()));
+                ts.append_one(Punct::new('>', Spacing::Joint).with_span(spans.end));
+            }
+        }
+    }
+    pub(crate) fn to_token_stream(&self, crate_kw: &Crate, ts: &mut TokenStream) {
+        self.to_token_stream_inner(ts, &|item_name, spans, ts| {
+            crate_kw.item_to_ts(item_name, spans, ts)
+        })
+    }
+
+    /// Outputs a path of field names as a `FieldPath` list,
+    /// eg: `FieldPath<TIdent<...>, FieldPath<Usize<0>, ()>>`
+    ///
+    /// With `markers`, identifiers are output as `TField<marker_type>`.
+    pub(crate) fn path_to_token_stream<'a, I>(
+        path: I,
+        crate_kw: &Crate,
+        mut markers: Option<&mut FieldMarkers>,
+        spans: Spans,
+        ts: &mut TokenStream,
+    ) where
+        I: IntoIterator<Item = &'a FieldName>,
+    {
+        let mut depth = 0;
+        for name in path {
+            crate_kw.item_to_ts("FieldPath", spans, ts);
+            ts.append_one(Punct::new('<', Spacing::Alone).with_span(spans.start));
+            match markers.as_deref_mut() {
+                Some(markers) => markers.name_to_token_stream(name, crate_kw, ts),
+                None => name.to_token_stream(crate_kw, ts),
+            }
+            tokenize_comma(spans.start, ts);
+            depth += 1;
+        }
+
+        ts.append_one(Group::new(Delimiter::Parenthesis, TokenStream::new()).with_span(spans.end));
+
+        for _ in 0..depth {
+            ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+        }
+    }
+
+    pub(crate) fn parse(input: ParseStream<'_>) -> Result<Self, Error> {
+        match input.next() {
+            Some(TokenTree::Literal(lit)) => match lit.to_string().parse::<usize>() {
+                Ok(x) => Ok(FieldName::Numeric(x, Spans::from_one(lit.span()))),
+                Err(_) => Err(Error::with_span(lit.span(), EXPECTED_FIELD_NAME)),
+            },
+            Some(TokenTree::Ident(ident)) => Ok(Self::from_ident(&ident)),
+            Some(tt) => Err(Error::with_span(tt.span(), EXPECTED_FIELD_NAME)),
+            None => Err(Error::with_span(Span::call_site(), EXPECTED_FIELD_NAME)),
+        }
+    }
+
+    /// Parses a `.`-separated path of field names, eg: `foo.bar.0`
+    pub(crate) fn parse_path(input: ParseStream<'_>) -> Result<Vec<Self>, Error> {
+        let mut path = Vec::new();
+
+        loop {
+            match input.peek() {
+                // `0.1` in `foo.0.1` is a float literal
+                Some(TokenTree::Literal(lit)) if lit.to_string().contains('.') => {
+                    let span = lit.span();
+                    for part in lit.to_string().split('.') {
+                        match part.parse::<usize>() {
+                            Ok(x) => path.push(FieldName::Numeric(x, Spans::from_one(span))),
+                            Err(_) => return Err(Error::with_span(span, EXPECTED_FIELD_NAME)),
+                        }
+                    }
+                    input.next();
+                }
+                _ => path.push(Self::parse(input)?),
+            }
+
+            match input.peek() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '.' => {
+                    input.next();
+                    if input.is_empty() {
+                        return Err(input.error("expected a field name after this"));
+                    }
+                }
+                _ => return Ok(path),
+            }
+        }
+    }
+
+    pub(crate) fn from_ident(ident: &Ident) -> Self {
+        let s = Rc::<str>::from(utils::ident_to_string_no_raw(&ident));
+        FieldName::Alphabetic(s, Spans::from_one(ident.span()))
+    }
+
+    /// Parses a field name in a string literal, eg: `"foo"`, `"r#type"`, `"0"`.
+    pub(crate) fn from_str_literal(lit: &Literal) -> Result<Self, Error> {
+        let spans = Spans::from_one(lit.span());
+        let err = || Error::new(spans, EXPECTED_STR_FIELD_NAME);
+
+        let lit_str = lit.to_string();
+        let name = lit_str
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .ok_or_else(err)?;
+        let name = name.strip_prefix("r#").unwrap_or(name);
+
+        let mut chars = name.chars();
+        match chars.next() {
+            Some('0'..='9') => name
+                .parse::<usize>()
+                .map(|n| FieldName::Numeric(n, spans))
+                .map_err(|_| err()),
+            Some(c) if (c.is_alphabetic() || c == '_') && name != "_" => {
+                if chars.all(|c| c.is_alphanumeric() || c == '_') {
+                    Ok(FieldName::Alphabetic(Rc::from(name), spans))
+                } else {
+                    Err(err())
+                }
+            }
+            _ => Err(err()),
+        }
+    }
+
+    /// The name of the hidden method that the `FieldType` derive generates
+    /// to read the field with this name.
+    pub(crate) fn accessor_ident(&self, span: Span) -> Ident {
+        let name = match self {
+            FieldName::Numeric(n, _) => n.to_string(),
+            FieldName::Alphabetic(name, _) => name.to_string(),
+            FieldName::NumericConst { .. } => unreachable!("not used in struct patterns"),
+        };
+        Ident::new(&format!("__multiconst_field_{}", name), span)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+// the cfg predicate for the existence of the item that this attribute is applied to,
+// where `meta` is the contents of a `#[...]` attribute.
+fn meta_cfg_predicate(meta: TokenStream) -> Option<TokenStream> {
+    let mut iter = meta.into_iter();
+    let name = iter.next()?;
+    let args = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+        _ => return None,
+    };
+
+    if name.is_ident("cfg") {
+        Some(args.stream())
+    } else if name.is_ident("cfg_attr") {
+        // `#[cfg_attr(pred, attrs...)]` is equivalent to
+        // `#[cfg(any(not(pred), all(attrs' predicates...)))]`
+        let mut args = split_commas(args.stream()).into_iter();
+        let cond = args.next()?;
+        let inner = args.filter_map(meta_cfg_predicate).collect::<Vec<_>>();
+        let inner = join_cfg_predicates("all", inner)?;
+
+        let not_cond = cfg_predicate_call("not", cond);
+        join_cfg_predicates("any", alloc::vec![not_cond, inner])
+    } else {
+        None
+    }
+}
+
+/// Removes the `#[doc]` and `#[deprecated]` attributes from a sequence of attributes.
+pub(crate) fn remove_doc_attrs(attrs: TokenStream) -> TokenStream {
+    let mut ts = TokenStream::new();
+    let mut iter = attrs.into_iter();
+
+    while let (Some(pound), Some(meta)) = (iter.next(), iter.next()) {
+        let is_doc = match &meta {
+            TokenTree::Group(group) => matches!(
+                group.stream().into_iter().next(),
+                Some(tt) if tt.is_ident("doc") || tt.is_ident("deprecated")
+            ),
+            _ => false,
+        };
+
+        if !is_doc {
+            ts.append_one(pound);
+            ts.append_one(meta);
+        }
+    }
+    ts
+}
+
+/// Combines the predicates with `all`/`any`, returning None if there's no predicates.
+pub(crate) fn join_cfg_predicates(func: &str, mut preds: Vec<TokenStream>) -> Option<TokenStream> {
+    match preds.len() {
+        0 => None,
+        1 => preds.pop(),
+        _ => {
+            let mut args = TokenStream::new();
+            for pred in preds {
+                args.extend(pred);
+                tokenize_comma(Span::call_site(), &mut args);
+            }
+            Some(cfg_predicate_call(func, args))
+        }
+    }
+}
+
+/// Outputs `func(args)`, eg: `not(feature = "foo")`
+pub(crate) fn cfg_predicate_call(func: &str, args: TokenStream) -> TokenStream {
+    TokenStream::from_array([
+        TokenTree::Ident(Ident::new(func, Span::call_site())),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, args)),
+    ])
+}
+
+// splits tokens on the commas that aren't nested in groups
+fn split_commas(ts: TokenStream) -> Vec<TokenStream> {
+    let mut out = Vec::new();
+    let mut curr = TokenStream::new();
+    for tt in ts {
+        if tt.is_punct(',') {
+            out.push(core::mem::take(&mut curr));
+        } else {
+            curr.append_one(tt);
+        }
+    }
+    if !curr.is_empty() {
+        out.push(curr);
+    }
+    out
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn tokenize_comma(span: Span, ts: &mut TokenStream) {
+    ts.append_one(Punct::new(',', Spacing::Alone).with_span(span));
+}
+
+pub(crate) fn tokenize_iter_delim<I, F>(
+    delimiter: Delimiter,
+    delim_span: Span,
+    iter: I,
+    ts: &mut TokenStream,
+    mut closure: F,
+) where
+    I: IntoIterator,
+    F: FnMut(&mut TokenStream, I::Item),
+{
+    let mut inner_ts = TokenStream::new();
+    for x in iter {
+        closure(&mut inner_ts, x);
+    }
+    let mut group = Group::new(delimiter, inner_ts);
+    group.set_span(delim_span);
+    ts.append_one(group)
+}
+
+pub(crate) fn tokenize_delim<F>(
+    delimiter: Delimiter,
+    delim_span: Span,
+    ts: &mut TokenStream,
+    closure: F,
+) where
+    F: FnOnce(&mut TokenStream),
+{
+    let mut inner_ts = TokenStream::new();
+    closure(&mut inner_ts);
+    let mut group = Group::new(delimiter, inner_ts);
+    group.set_span(delim_span);
+    ts.append_one(group)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Spans {
+    pub(crate) start: Span,
+    pub(crate) end: Span,
+}
+
+impl Spans {
+    pub(crate) fn from_one(span: Span) -> Self {
+        Self {
+            start: span,
+            end: span,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn tokenize_seq_length_assoc_const(
+    crate_kw: &Crate,
+    spans: Spans,
+    type_: TokenStream,
+) -> TokenStream {
+    let mut ts = TokenStream::new();
+
+    ts.append_one(Punct::new('<', Spacing::Joint).with_span(spans.start));
+    ts.append_one(Group::new(Delimiter::Parenthesis, type_).with_span(spans.start));
+    ts.append_one(Ident::new("as", spans.start));
+    crate_kw.item_to_ts("SeqLength", spans, &mut ts);
+    ts.append_one(Punct::new('>', Spacing::Alone).with_span(spans.end));
+    ts.append_one(Punct::new(':', Spacing::Joint).with_span(spans.end));
+    ts.append_one(Punct::new(':', Spacing::Alone).with_span(spans.end));
+    ts.append_one(Ident::new("LENGTH", spans.end));
+
+    ts
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) struct Crate {
+    pub(crate) ident: Ident,
+}
+
+impl Crate {
+    #[cfg(test)]
+    pub(crate) fn new_dummy() -> Self {
+        Crate {
+            ident: Ident::new("crate", Span::call_site()),
+        }
+    }
+
+    /// outputs the path to the item at `multiconst::__::{item}`
+    pub(crate) fn item_to_ts(&self, item: &str, spans: Spans, ts: &mut TokenStream) {
+        ts.append_array([
+            TokenTree::Ident(self.ident.clone().with_span(spans.start)),
+            Punct::new(':', Spacing::Joint)
+                .with_span(spans.start)
+                .into(),
+            Punct::new(':', Spacing::Alone)
+                .with_span(spans.start)
+                .into(),
+            Ident::new("__", spans.start).into(),
+            Punct::new(':', Spacing::Joint)
+                .with_span(spans.start)
+                .into(),
+            Punct::new(':', Spacing::Alone)
+                .with_span(spans.start)
+                .into(),
+            Ident::new(item, spans.end).into(),
+        ]);
+    }
+
+    pub(crate) fn parse(input: ParseStream<'_>) -> Result<Self, Error> {
+        match input.parse_ident() {
+            Ok(ident) if ident.which_ident_in(&["$crate", "crate"]).is_some() => {
+                Ok(Crate { ident })
+            }
+            Ok(x) => Err(Error::new(
+                Spans::from_one(x.span()),
+                "Expected `$crate` or `crate`",
+            )),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(feature = "__derive")]
+impl quote::ToTokens for Crate {
+    fn to_tokens(&self, ts: &mut used_proc_macro::TokenStream) {
+        self.ident.to_tokens(ts);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+#[derive(Clone)]
+pub(crate) struct OpaqueType {
+    pub(crate) spans: Spans,
+    pub(crate) ty: TokenStream,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Loosely parsed `#[...]`-style attributes
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+#[derive(Clone)]
+pub(crate) struct Attributes {
+    pub(crate) attrs: TokenStream,
+    pub(crate) spans: Spans,
+}
+
+impl Attributes {
+    pub(crate) fn new() -> Self {
+        Self {
+            attrs: TokenStream::new(),
+            spans: Spans::from_one(Span::call_site()),
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> Self {
+        let attrs = core::mem::replace(&mut self.attrs, TokenStream::new());
+        Self {
+            attrs,
+            spans: self.spans,
+        }
+    }
+
+    pub(crate) fn ensure_used(&self) -> Result<(), Error> {
+        if self.attrs.is_empty() {
+            Ok(())
+        } else {
+            self.unused_error()
+        }
+    }
+
+    pub(crate) fn unused_error<T>(&self) -> Result<T, Error> {
+        Err(Error::new(self.spans, "these attributes are unused"))
+    }
+
+    /// Appens an attribute that comes after `self` into `self`.
+    pub(crate) fn append(&mut self, other: Attributes) {
+        if self.attrs.is_empty() {
+            *self = other;
+        } else {
+            self.attrs.extend(other.attrs);
+            self.spans.end = other.spans.end;
+        }
+    }
+
+    /// Iterates over the contents of the `#[...]` attributes
+    fn metas(&self) -> impl Iterator<Item = Group> {
+        self.attrs.clone().into_iter().filter_map(|tt| match tt {
+            TokenTree::Group(group) => Some(group),
+            _ => None,
+        })
+    }
+
+    /// Gets the `#[cfg(...)]` and `#[cfg_attr(...)]` attributes.
+    pub(crate) fn cfg_attrs(&self) -> TokenStream {
+        let mut ts = TokenStream::new();
+        for meta in self.metas() {
+            if matches!(meta.stream().into_iter().next(), Some(tt) if tt.is_ident("cfg") || tt.is_ident("cfg_attr"))
+            {
+                ts.append_one(Punct::new('#', Spacing::Alone).with_span(meta.span()));
+                ts.append_one(meta);
+            }
+        }
+        ts
+    }
+
+    /// Whether the `#[cfg]`/`#[cfg_attr]` attributes can remove the item
+    /// that these attributes are applied to.
+    pub(crate) fn is_conditional(&self) -> bool {
+        self.cfg_predicate().is_some()
+    }
+
+    /// Gets the cfg predicate that's true when the item
+    /// that these attributes are applied to exists,
+    /// returning None if the `#[cfg]`/`#[cfg_attr]` attributes don't affect that.
+    pub(crate) fn cfg_predicate(&self) -> Option<TokenStream> {
+        let preds: Vec<TokenStream> = self
+            .metas()
+            .filter_map(|meta| meta_cfg_predicate(meta.stream()))
+            .collect();
+
+        join_cfg_predicates("all", preds)
+    }
+
+    pub(crate) fn parse(input: ParseStream<'_>) -> Self {
+        let mut ts = TokenStream::new();
+
+        let start = input.span();
+
+        while matches!(
+            input.peekn(2),
+            [tt0, tt1]
+            if tt0.is_punct('#') && tt1.is_group(Delimiter::Bracket)
+        ) {
+            ts.extend(input.next());
+            ts.extend(input.next());
+        }
+
+        let end = input.last_span();
+
+        Self {
+            attrs: ts,
+            spans: Spans { start, end },
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    expand_to, infer_types, parse_pattern,
+    test_utils::{remove_whitespaces, StrExt},
+    Location,
+};
+
+use used_proc_macro::{Ident, Span, TokenStream};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+fn multiconst() -> Ident {
+    Ident::new("multiconst", Span::call_site())
+}
+
+fn infer_str(pattern: &str, type_: &str) -> Result<Vec<(String, String, String)>, String> {
+    let pattern = parse_pattern(pattern.parse().unwrap())
+        .map_err(|e| e.into_compile_error(&multiconst()).to_string())?;
+
+    let typed = infer_types(&multiconst(), &pattern, type_.parse().unwrap())
+        .map_err(|e| e.into_compile_error(&multiconst()).to_string())?;
+
+    Ok(typed
+        .bindings()
+        .iter()
+        .map(|b| {
+            (
+                b.name().to_string(),
+                remove_whitespaces(&b.type_().to_string()),
+                b.field_path().to_string(),
+            )
+        })
+        .collect())
+}
+
+fn triple(name: &str, type_: &str, path: &str) -> (String, String, String) {
+    (name.to_string(), type_.to_string(), path.to_string())
+}
+
+#[test]
+fn parse_pattern_test() {
+    assert!(parse_pattern("[A, (B, _), C @ ..]".parse().unwrap()).is_ok());
+
+    let err = parse_pattern("(A, B) C".parse().unwrap())
+        .err()
+        .unwrap()
+        .into_compile_error(&multiconst())
+        .to_string();
+    assert!(
+        err.consecutive_in_self(&["expected no more tokens"]),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn infer_types_test() {
+    {
+        let err = infer_str("(A, [B, C @ ..])", "(u8, [u16; _])").unwrap_err();
+        assert!(err.consecutive_in_self(&["infer", "length"]), "{}", err);
+    }
+    {
+        let err = infer_str("(A, .., B)", "Foo").unwrap_err();
+        assert!(err.consecutive_in_self(&["doesn't support"]), "{}", err);
+    }
+    assert_eq!(
+        infer_str(
+            "(A, [B, C @ ..: &[u16]], [D, E])",
+            "(u8, [u16; 4], [u32; _])"
+        )
+        .unwrap(),
+        [
+            triple("A", "u8", ".0"),
+            triple("B", "u16", ".1[0]"),
+            triple("C", "&'static[u16]", ".1[1..]"),
+            triple("D", "u32", ".2[0]"),
+            triple("E", "u32", ".2[1]"),
+        ],
+    );
+    assert_eq!(
+        infer_str("[A, B @ ..]", "&[u8]").unwrap(),
+        [
+            triple("A", "u8", "[0]"),
+            triple("B", "&'static[u8]", "[1..]"),
+        ],
+    );
+
+    let err = infer_str("(A, B)", "[u8; 2]").unwrap_err();
+    assert!(
+        err.consecutive_in_self(&["mismatched pattern and type"]),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn infer_types_length_test() {
+    let pattern = parse_pattern("[[A, B], [C, D]]".parse().unwrap()).unwrap();
+    let typed = infer_types(&multiconst(), &pattern, "[[u8; _]; _]".parse().unwrap()).unwrap();
+
+    assert_eq!(remove_whitespaces(&typed.type_().to_string()), "[[u8;2];2]");
+}
+
+#[test]
+fn expand_to_test() {
+    let expand = |location, input: &str| {
+        let mut out = TokenStream::new();
+        expand_to(&multiconst(), location, input.parse().unwrap(), &mut out)
+            .map(|()| out.to_string())
+            .map_err(|e| e.into_compile_error(&multiconst()).to_string())
+    };
+
+    {
+        let out = expand(Location::Module, "pub const [A, B]: [u8; _] = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const __PRIV_MULTICONST__A: (u8, u8,)",
+                "pub const A: u8 = __PRIV_MULTICONST__A.0;",
+                "pub const B: u8 = __PRIV_MULTICONST__A.1;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = expand(Location::InherentImpl, "const (A, B): (u8, u16) = expr;").unwrap();
+        assert!(
+            out.consecutive_unspace(&[
+                "const A: u8 = Self::__PRIV_MULTICONST__A.0;",
+                "const B: u16 = Self::__PRIV_MULTICONST__A.1;",
+            ]),
+            "{}",
+            out
+        );
+    }
+    {
+        let out = expand(Location::Module, "const [A, ..]: [u8; _] = expr;").unwrap_err();
+        assert!(
+            out.consecutive_in_self(&["multiconst", "compile_error", "infer", "length"]),
+            "{}",
+            out
+        );
+    }
+}
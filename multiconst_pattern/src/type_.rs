@@ -7,7 +7,7 @@ use alloc::{boxed::Box, vec, vec::Vec};
 use crate::{
     parsing::{ParseBuffer, ParseStream},
     syntax::{tokenize_delim, tokenize_iter_delim, OpaqueType, Spans},
-    utils::{TokenStreamExt, WithSpan},
+    utils::{TokenStreamExt, TokenTreeExt, WithSpan},
     Error,
 };
 
@@ -16,6 +16,8 @@ use crate::{
 pub(crate) enum Type<L> {
     Underscore(Span),
     Array(ArrayType<L>),
+    /// A reference to a slice, eg: `&[u8]`
+    Slice(SliceType<L>),
     Tuple(TupleType<L>),
     Opaque(OpaqueType),
 }
@@ -31,6 +33,16 @@ pub(crate) struct ArrayType<L = TokenStream> {
     pub(crate) len: L,
 }
 
+#[cfg_attr(feature = "__dbg", derive(Debug))]
+#[derive(Clone)]
+pub(crate) struct SliceType<L = TokenStream> {
+    pub(crate) ampersand: Span,
+    /// The lifetime of the reference, eg: `'static`
+    pub(crate) lifetime: TokenStream,
+    pub(crate) brackets: Span,
+    pub(crate) elem_ty: Box<Type<L>>,
+}
+
 #[cfg_attr(feature = "__dbg", derive(Debug))]
 #[derive(Clone)]
 pub(crate) struct TupleType<L = TokenStream> {
@@ -47,6 +59,14 @@ impl<L> Type<L> {
             | Type::Tuple(TupleType {
                 parentheses: span, ..
             }) => Spans::from_one(*span),
+            Type::Slice(SliceType {
+                ampersand,
+                brackets,
+                ..
+            }) => Spans {
+                start: *ampersand,
+                end: *brackets,
+            },
             Type::Opaque(x) => x.spans,
         }
     }
@@ -54,6 +74,7 @@ impl<L> Type<L> {
         match self {
             Type::Underscore(span)
             | Type::Array(ArrayType { brackets: span, .. })
+            | Type::Slice(SliceType { brackets: span, .. })
             | Type::Tuple(TupleType {
                 parentheses: span, ..
             }) => *span,
@@ -79,6 +100,12 @@ impl ParsedType {
                     len,
                 }))
             }
+            Type::Slice(slice_ty) => Ok(Type::Slice(SliceType {
+                ampersand: slice_ty.ampersand,
+                lifetime: slice_ty.lifetime,
+                brackets: slice_ty.brackets,
+                elem_ty: Box::new(slice_ty.elem_ty.definite_length_real_type()?),
+            })),
             Type::Tuple(tup_ty) => Ok(Type::Tuple(TupleType {
                 parentheses: tup_ty.parentheses,
                 elem_tys: tup_ty
@@ -159,6 +186,8 @@ impl ParsedType {
                 }
                 Delimiter::None => return Type::parse(&mut ParseBuffer::new(group.stream())),
             }
+        } else if let Some(slice_ty) = SliceType::parse(input)? {
+            Ok(Type::Slice(slice_ty))
         } else if let Some(TokenTree::Literal(lit)) = input.peek() {
             Err(Error::with_span(lit.span(), "expected type, found literal"))
         } else if let Some(ident) = input.peek_parse_keyword("_") {
@@ -180,6 +209,7 @@ impl RealType {
     pub(crate) fn to_token_stream(&self, ts: &mut TokenStream) {
         match self {
             Type::Array(arr_ty) => arr_ty.to_token_stream(ts),
+            Type::Slice(slice_ty) => slice_ty.to_token_stream(ts),
             Type::Tuple(tup_ty) => tup_ty.to_token_stream(ts),
             Type::Opaque(OpaqueType { ty, .. }) => ts.extend(ty.clone()),
             Type::Underscore(span) => ts.append_one(Ident::new("_", *span)),
@@ -208,6 +238,68 @@ impl ArrayType {
     }
 }
 
+impl SliceType<Option<TokenStream>> {
+    /// Parses a `&[T]`/`&'lifetime [T]` type,
+    /// returning None (without consuming tokens) if the type is not a slice reference.
+    fn parse(input: ParseStream<'_>) -> Result<Option<Self>, Error> {
+        let lifetime_len = match input.peekn(4) {
+            [amp, ..] if !amp.is_punct('&') => return Ok(None),
+            [_, quote, lt, _] if quote.is_punct('\'') && matches!(lt, TokenTree::Ident(_)) => 2,
+            _ => 0,
+        };
+
+        let is_slice = match input.peekn(lifetime_len + 3).get(lifetime_len + 1..) {
+            Some([TokenTree::Group(group), rest @ ..]) => {
+                group.delimiter() == Delimiter::Bracket
+                    && !group.stream().into_iter().any(|tt| tt.is_punct(';'))
+                    && rest.iter().all(ParseBuffer::is_type_terminator)
+            }
+            _ => false,
+        };
+        if !is_slice {
+            return Ok(None);
+        }
+
+        let ampersand = input.next().unwrap().span();
+        let lifetime = (0..lifetime_len).filter_map(|_| input.next()).collect();
+        let group = match input.next() {
+            Some(TokenTree::Group(group)) => group,
+            _ => unreachable!("{}", core::panic::Location::caller()),
+        };
+
+        let elem_ty = Box::new(Type::parse(&mut ParseBuffer::new(group.stream()))?);
+
+        Ok(Some(SliceType {
+            ampersand,
+            lifetime,
+            brackets: group.span(),
+            elem_ty,
+        }))
+    }
+}
+
+impl SliceType {
+    pub(crate) fn to_token_stream(&self, ts: &mut TokenStream) {
+        let SliceType {
+            ampersand,
+            lifetime,
+            brackets,
+            elem_ty,
+        } = self;
+
+        ts.append_one(Punct::new('&', Spacing::Alone).with_span(*ampersand));
+        if lifetime.is_empty() {
+            ts.append_one(Punct::new('\'', Spacing::Joint).with_span(*ampersand));
+            ts.append_one(Ident::new("static", *ampersand));
+        } else {
+            ts.extend(lifetime.clone());
+        }
+        tokenize_delim(Delimiter::Bracket, *brackets, ts, |ts| {
+            elem_ty.to_token_stream(ts);
+        });
+    }
+}
+
 impl TupleType {
     pub(crate) fn to_token_stream(&self, ts: &mut TokenStream) {
         let TupleType {
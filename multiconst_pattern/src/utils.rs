@@ -0,0 +1,470 @@
+use used_proc_macro::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+
+use alloc::{
+    collections::VecDeque,
+    string::{String, ToString},
+};
+
+use core::iter::{FromIterator, Fuse};
+
+//////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub(crate) struct PeekableN<I: Iterator> {
+    iter: Fuse<I>,
+    queue: VecDeque<I::Item>,
+}
+
+impl PeekableN<core::ops::Range<u8>> {
+    #[allow(clippy::new_ret_no_self)]
+    pub(crate) fn new<I: IntoIterator>(iter: I) -> PeekableN<I::IntoIter> {
+        PeekableN {
+            iter: iter.into_iter().fuse(),
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator> PeekableN<I> {
+    pub(crate) fn peek(&mut self) -> Option<&I::Item> {
+        if self.queue.is_empty() {
+            self.queue.push_back(self.iter.next()?);
+        }
+        Some(&self.queue[0])
+    }
+    pub(crate) fn peekn(&mut self, n: usize) -> &[I::Item] {
+        while self.queue.len() < n {
+            match self.iter.next() {
+                Some(x) => self.queue.push_back(x),
+                None => break,
+            }
+        }
+
+        let end = core::cmp::min(self.queue.len(), n);
+        &self.queue.make_contiguous()[..end]
+    }
+}
+
+impl<I> Iterator for PeekableN<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let opt @ Some(_) = self.queue.pop_front() {
+            opt
+        } else {
+            self.iter.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let len = self.queue.len();
+        (low + len, high.map(|x| x.saturating_add(len)))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) fn ident_to_string_no_raw(ident: &Ident) -> String {
+    let mut string = ident.to_string();
+    if string.starts_with("r#") {
+        string.drain(..2);
+    }
+    string
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Converts `ts` to a string,
+/// with less whitespace than the `Display` impl of `TokenStream`.
+///
+/// This is only meant for showing code to users,
+/// the spacing between tokens is guessed.
+pub(crate) fn tokens_to_compact_string(ts: TokenStream) -> String {
+    let mut out = String::new();
+    write_compact_tokens(ts, &mut out);
+    out
+}
+
+const MULTI_CHAR_OPS: &[&str] = &[
+    "::", "..", "..=", "...", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=",
+    "/=", "%=", "^=", "&=", "|=", "<<", ">>", "<<=", ">>=",
+];
+
+// keywords that are followed by a pattern or expression
+const KEYWORDS_BEFORE_EXPRS: &[&str] = &[
+    "let", "mut", "ref", "in", "if", "match", "while", "return", "break",
+];
+
+#[derive(Copy, Clone, PartialEq)]
+enum PrevToken {
+    Nothing,
+    Word,
+    Punct,
+}
+
+fn write_compact_tokens(ts: TokenStream, out: &mut String) {
+    let mut iter = PeekableN::new(ts);
+    let mut prev = PrevToken::Nothing;
+    // whether the previous token requested a space after it
+    let mut space = false;
+    // how many `::<` generic argument lists are open
+    let mut generic_depth = 0u32;
+    let mut prev_op = String::new();
+    // whether the previous token is an identifier that starts with an uppercase letter,
+    // which is assumed to be a type name followed by generic arguments.
+    let mut prev_type_name = false;
+
+    while let Some(tt) = iter.next() {
+        let is_type_name = matches!(
+            &tt,
+            TokenTree::Ident(ident)
+            if ident_to_string_no_raw(ident).starts_with(|c: char| c.is_ascii_uppercase())
+        );
+
+        match tt {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if space || prev == PrevToken::Word {
+                    out.push(' ');
+                }
+                out.push_str(&tt.to_string());
+                prev = PrevToken::Word;
+                // so that `let [a, b]` isn't written like the `a[b]` indexing expression
+                space = tt.which_ident_in(KEYWORDS_BEFORE_EXPRS).is_some();
+            }
+            TokenTree::Group(group) => {
+                let delim = group.delimiter();
+                if space || (prev == PrevToken::Word && delim == Delimiter::Brace) {
+                    out.push(' ');
+                }
+
+                let (open, close) = match delim {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                let inner = tokens_to_compact_string(group.stream());
+                if inner.is_empty() {
+                    out.push_str(open.trim_end());
+                    out.push_str(close.trim_start());
+                } else {
+                    out.push_str(open);
+                    out.push_str(&inner);
+                    out.push_str(close);
+                }
+
+                prev = PrevToken::Word;
+                space = false;
+            }
+            TokenTree::Punct(punct) => {
+                let mut op = String::new();
+                op.push(punct.as_char());
+                let mut spacing = punct.spacing();
+                while spacing == Spacing::Joint {
+                    match iter.peek() {
+                        Some(TokenTree::Punct(next)) => {
+                            op.push(next.as_char());
+                            if !MULTI_CHAR_OPS.contains(&&*op) {
+                                op.pop();
+                                break;
+                            }
+                            spacing = next.spacing();
+                            iter.next();
+                        }
+                        _ => break,
+                    }
+                }
+
+                let after_word = prev == PrevToken::Word;
+                let (before, after) = match &*op {
+                    "." | "::" | ".." | "..=" => (false, false),
+                    "," | ";" | ":" => (false, true),
+                    "'" | "#" | "$" => (after_word, false),
+                    "!" if after_word => (false, false),
+                    "&" | "&&" | "!" | "*" | "-" if !after_word => (false, false),
+                    "<" if prev_op == "::" || prev_type_name => {
+                        generic_depth += 1;
+                        (false, false)
+                    }
+                    ">" if generic_depth != 0 => {
+                        generic_depth -= 1;
+                        // no space between a trailing comma and the `>`
+                        space = false;
+                        (false, false)
+                    }
+                    _ => (true, true),
+                };
+
+                if prev != PrevToken::Nothing && (before || space) {
+                    out.push(' ');
+                }
+                out.push_str(&op);
+
+                prev = PrevToken::Punct;
+                space = after;
+                prev_op = op;
+                prev_type_name = false;
+                continue;
+            }
+        }
+        prev_op.clear();
+        prev_type_name = is_type_name;
+    }
+}
+
+/// Converts `ts` to a string, with each item and statement on its own line,
+/// and the contents of `{}` braces indented.
+///
+/// This is only meant for showing code to users,
+/// the tokens within a line are spaced like in [`tokens_to_compact_string`].
+pub(crate) fn tokens_to_pretty_string(ts: TokenStream) -> String {
+    let mut out = String::new();
+    write_pretty_tokens(ts, 0, &mut out);
+    out
+}
+
+fn write_pretty_tokens(ts: TokenStream, indent: usize, out: &mut String) {
+    // the tokens of the current line that haven't been written yet
+    let mut line = TokenStream::new();
+    // whether the last thing written is the `}` of a block, in the current line
+    let mut after_brace = false;
+    let mut prev_is_pound = false;
+
+    for tt in ts {
+        if after_brace {
+            let continues_line = match &tt {
+                TokenTree::Punct(p) => matches!(p.as_char(), ';' | ',' | '.' | '?'),
+                TokenTree::Ident(ident) => ident.is_ident("else"),
+                _ => false,
+            };
+            if !continues_line {
+                out.push('\n');
+                after_brace = false;
+            }
+        }
+
+        let is_pound = matches!(&tt, TokenTree::Punct(p) if p.as_char() == '#');
+
+        match tt {
+            TokenTree::Group(group) if is_multiline_block(&group) => {
+                if after_brace || !line.is_empty() {
+                    write_pretty_line(&mut line, indent, &mut after_brace, out);
+                    out.push(' ');
+                } else {
+                    write_indentation(indent, out);
+                }
+
+                out.push('{');
+                let stream = group.stream();
+                if !stream.is_empty() {
+                    out.push('\n');
+                    write_pretty_tokens(stream, indent + 1, out);
+                    write_indentation(indent, out);
+                }
+                out.push('}');
+                after_brace = true;
+            }
+            TokenTree::Group(group) if prev_is_pound && group.delimiter() == Delimiter::Bracket => {
+                // attributes are written on their own line
+                line.append_one(group);
+                write_pretty_line(&mut line, indent, &mut after_brace, out);
+                out.push('\n');
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                line.append_one(punct);
+                write_pretty_line(&mut line, indent, &mut after_brace, out);
+                out.push('\n');
+            }
+            tt => line.append_one(tt),
+        }
+
+        prev_is_pound = is_pound;
+    }
+
+    if after_brace || !line.is_empty() {
+        write_pretty_line(&mut line, indent, &mut after_brace, out);
+        out.push('\n');
+    }
+}
+
+// whether `group` is a `{}` block that contains statements, items, or other blocks,
+// `{}` braces that don't are written in a single line.
+fn is_multiline_block(group: &Group) -> bool {
+    group.delimiter() == Delimiter::Brace
+        && group.stream().into_iter().any(|tt| match tt {
+            TokenTree::Punct(punct) => punct.as_char() == ';',
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+            _ => false,
+        })
+}
+
+// writes the tokens in `line` without a trailing newline, clearing it
+fn write_pretty_line(
+    line: &mut TokenStream,
+    indent: usize,
+    after_brace: &mut bool,
+    out: &mut String,
+) {
+    let text = tokens_to_compact_string(core::mem::replace(line, TokenStream::new()));
+
+    if *after_brace {
+        if !text.is_empty() && !text.starts_with(&[';', ',', '.', '?'][..]) {
+            out.push(' ');
+        }
+    } else if !text.is_empty() {
+        write_indentation(indent, out);
+    }
+
+    out.push_str(&text);
+    *after_brace = false;
+}
+
+fn write_indentation(indent: usize, out: &mut String) {
+    for _ in 0..indent {
+        out.push_str("    ");
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) trait TokenStreamExt: Sized {
+    fn as_mut_token_stream(&mut self) -> &mut TokenStream;
+
+    fn from_array<T, const N: usize>(array: [T; N]) -> Self
+    where
+        T: Into<TokenTree>;
+
+    #[inline(always)]
+    fn append_array<T, const N: usize>(&mut self, array: [T; N])
+    where
+        T: Into<TokenTree>,
+    {
+        // msrv is 1.51.0, before [T; N] impld IntoIterator
+        #![allow(deprecated)]
+        self.as_mut_token_stream()
+            .extend(core::array::IntoIter::new(array).map(Into::<TokenTree>::into))
+    }
+
+    #[inline(always)]
+    fn append_one<T>(&mut self, elem: T)
+    where
+        T: Into<TokenTree>,
+    {
+        let tt: TokenTree = elem.into();
+        self.as_mut_token_stream().extend(core::iter::once(tt))
+    }
+
+    fn append_keyword(&mut self, kw: &str, span: Span) {
+        self.append_one(Ident::new(kw, span))
+    }
+}
+
+impl TokenStreamExt for TokenStream {
+    fn from_array<T, const N: usize>(array: [T; N]) -> Self
+    where
+        T: Into<TokenTree>,
+    {
+        // msrv is 1.51.0, before [T; N] impld IntoIterator
+        #![allow(deprecated)]
+
+        TokenStream::from_iter(core::array::IntoIter::new(array).map(Into::<TokenTree>::into))
+    }
+
+    #[inline(always)]
+    fn as_mut_token_stream(&mut self) -> &mut TokenStream {
+        self
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) trait TokenTreeExt: Sized {
+    fn as_token_tree(&self) -> &TokenTree;
+
+    fn is_punct(&self, c: char) -> bool {
+        matches!(self.as_token_tree(), TokenTree::Punct(p)  if p.as_char() == c)
+    }
+
+    fn is_group(&self, delim: Delimiter) -> bool {
+        matches!(
+            self.as_token_tree(),
+            TokenTree::Group(g) if g.delimiter() == delim
+        )
+    }
+}
+
+impl TokenTreeExt for TokenTree {
+    fn as_token_tree(&self) -> &TokenTree {
+        self
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) trait WithSpan {
+    // only changes where error messages are printed
+    fn with_span(self, span: Span) -> Self;
+}
+
+macro_rules! impl_with_span {
+    ($ty:ty) => {
+        impl WithSpan for $ty {
+            fn with_span(mut self, span: Span) -> Self {
+                let old_span = self.span();
+                self.set_span(old_span.located_at(span));
+                self
+            }
+        }
+    };
+}
+impl_with_span! {::used_proc_macro::Group}
+impl_with_span! {::used_proc_macro::Ident}
+impl_with_span! {::used_proc_macro::Literal}
+impl_with_span! {::used_proc_macro::Punct}
+impl_with_span! {::used_proc_macro::TokenTree}
+
+impl WithSpan for TokenStream {
+    fn with_span(self, span: Span) -> TokenStream {
+        self.into_iter().map(|tt| tt.with_span(span)).collect()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub(crate) trait IsIdent: Sized {
+    fn is_ident(&self, ident: &str) -> bool;
+
+    fn which_ident_in(&self, idents: &[&str]) -> Option<usize>;
+}
+
+impl IsIdent for Ident {
+    fn is_ident(&self, ident: &str) -> bool {
+        ident_to_string_no_raw(self) == ident
+    }
+
+    fn which_ident_in(&self, idents: &[&str]) -> Option<usize> {
+        let this = ident_to_string_no_raw(self);
+
+        idents.iter().position(|x| **x == this)
+    }
+}
+
+impl IsIdent for TokenTree {
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(
+            self.as_token_tree(),
+            TokenTree::Ident(x)
+            if x.is_ident(ident)
+        )
+    }
+    fn which_ident_in(&self, idents: &[&str]) -> Option<usize> {
+        match self.as_token_tree() {
+            TokenTree::Ident(x) => x.which_ident_in(idents),
+            _ => None,
+        }
+    }
+}
@@ -1,74 +1,56 @@
+//! The proc macros of `multiconst`,
+//! implemented in the `multiconst_pattern` crate.
+//!
+//! Unless the `derive` feature is enabled,
+//! `multiconst_pattern` is used without its `proc_macro2`-based API,
+//! so that these macros only depend on `proc_macro`.
 #![no_std]
 #![deny(unused_must_use)]
 #![forbid(unsafe_code)]
 
-extern crate alloc;
+extern crate proc_macro;
 
-#[cfg(not(any(test, feature = "derive")))]
-extern crate proc_macro as used_proc_macro;
+use multiconst_pattern::__private as imp;
 
-#[cfg(any(test, feature = "derive"))]
-extern crate proc_macro2 as used_proc_macro;
+use proc_macro::TokenStream;
 
-#[cfg(any(feature = "__dbg", test))]
-extern crate std;
-
-#[cfg(feature = "derive")]
-mod derive_macro;
-
-mod error;
-mod for_field_macros;
-mod for_multiconst_macro;
-mod parsing;
-mod pattern;
-mod pattern_processing;
-mod syntax;
-mod type_;
-mod utils;
+#[proc_macro]
+pub fn __priv_multiconst_proc_macro(args: TokenStream) -> TokenStream {
+    imp::multiconst_macro(args.into()).into()
+}
 
-#[cfg(test)]
-mod test_utils;
+#[proc_macro]
+pub fn __priv_associated_multiconst_proc_macro(args: TokenStream) -> TokenStream {
+    imp::associated_multiconst_macro(args.into()).into()
+}
 
-use crate::{error::Error, for_multiconst_macro::Usedwhere};
+#[proc_macro]
+pub fn __priv_extension_multiconst_proc_macro(args: TokenStream) -> TokenStream {
+    imp::extension_multiconst_macro(args.into()).into()
+}
 
 #[proc_macro]
-pub fn __priv_multiconst_proc_macro(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    crate::for_multiconst_macro::macro_impl(args.into(), Usedwhere::OutsideImpls)
-        .unwrap_or_else(|e| e)
-        .into()
+pub fn __priv_multiconst_enum_proc_macro(args: TokenStream) -> TokenStream {
+    imp::multiconst_enum_macro(args.into()).into()
 }
 
 #[proc_macro]
-pub fn __priv_associated_multiconst_proc_macro(
-    args: proc_macro::TokenStream,
-) -> proc_macro::TokenStream {
-    crate::for_multiconst_macro::macro_impl(args.into(), Usedwhere::InherentImpl)
-        .unwrap_or_else(|e| e)
-        .into()
+pub fn __priv_field_proc_macro(args: TokenStream) -> TokenStream {
+    imp::field_macro(args.into()).into()
 }
 
 #[proc_macro]
-pub fn __priv_field_proc_macro(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    crate::for_field_macros::field_macro_impl(args.into())
-        .unwrap_or_else(|e| e)
-        .into()
+pub fn __priv_field_path_proc_macro(args: TokenStream) -> TokenStream {
+    imp::field_path_macro(args.into()).into()
 }
 
 #[proc_macro]
-pub fn __priv_field_name_aliases_proc_macro(
-    args: proc_macro::TokenStream,
-) -> proc_macro::TokenStream {
-    crate::for_field_macros::field_name_aliases_macro_impl(args.into())
-        .unwrap_or_else(|e| e)
-        .into()
+pub fn __priv_field_name_aliases_proc_macro(args: TokenStream) -> TokenStream {
+    imp::field_name_aliases_macro(args.into()).into()
 }
 
 #[cfg(feature = "derive")]
 #[proc_macro_derive(FieldType, attributes(field_type))]
-pub fn field_type_derive(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ret = crate::derive_macro::derive_macro_impl(args.into())
-        .unwrap_or_else(|e| e.to_compile_error())
-        .into();
-
-    ret
+pub fn field_type_derive(args: TokenStream) -> TokenStream {
+    imp::field_type_derive(args.into()).into()
 }
@@ -1,5 +1,5 @@
 /**
-Derives the [`FieldType`] trait for a struct.
+Derives the [`FieldType`] trait for a struct, enum, or union.
 
 # Generated code
 
@@ -11,6 +11,19 @@ The condition is that a field is one of:
 
 "non-`pub`" includes `pub(crate)` and smaller visibilities.
 
+For enums, the impls are for the fields of every variant,
+and the field names are [`VariantField`]s of the variant and field names,
+eg: `FieldType<VariantField<field_name!(Circle), field_name!(radius)>>`.
+The fields of enums have the same visibility as the enum,
+so by default, impls are generated for all of them.
+
+The fields of unions follow the same visibility rules as the fields of structs,
+the [`multiconst`](crate::multiconst) macros read union fields with
+[union patterns](crate::multiconst#union-patterns).
+
+For structs and unions, this also implements [`FieldList`],
+which lists the fields that [`FieldType`] is implemented for.
+
 Whether [`FieldType`] is implemented for a field can be overridden with
 the [`#[field_type(pub)]`](#pub-attr) and
 [`#[field_type(priv)]`](#priv-attr) attributes.
@@ -28,7 +41,7 @@ for when the `multiconst` crate is reexported at some path.
 
 # Container or field Attributes
 
-Attributes that can go above the struct and fields.
+Attributes that can go above the struct, enum, or union, enum variants, and fields.
 
 <span id = "pub-attr"></span>
 ### `#[field_type(pub)]`
@@ -38,6 +51,9 @@ Tells the macro to generate a [`FieldType`] impl for the field.
 When this is used above the struct,
 it tells the macro to generate impls of [`FieldType`] for all fields.
 
+When this is used above an enum variant,
+it tells the macro to generate impls of [`FieldType`] for all fields of the variant.
+
 When this is used on a field,
 it tells the macro to generate an impl of [`FieldType`] for that field,
 overriding [`#[field_type(priv)]`](#priv-attr) attributes on the struct.
@@ -52,12 +68,79 @@ Tells the macro to **not** generate a [`FieldType`] impl for the field.
 When this is used above the struct,
 it tells the macro to not generate impls of [`FieldType`] for any fields.
 
+When this is used above an enum variant,
+it tells the macro to not generate impls of [`FieldType`] for any fields of the variant.
+
 When this is used on a field,
 it tells the macro to **not** generate an impl of [`FieldType`] for that field,
 overriding [`#[field_type(pub)]`](#pub-attr) attributes on the struct.
 
 [example that uses this attribute](#vis-example)
 
+# Field Attributes
+
+Attributes that go above fields.
+
+<span id = "rename-attr"></span>
+### `#[field_type(rename = "foo")]`
+
+Makes the field be listed in [`FieldList`] as `foo`, instead of the name of the field,
+and generates an impl of [`FieldType`] with `foo` as the field name.
+The name can be an identifier (raw or not, eg: `"r#type"` is the same as `"type"`),
+or an integer.
+
+No [`FieldType`] impl is generated for the Rust name of the field,
+so the [`multiconst`](crate::multiconst) macros can only destructure the field
+by that name with a type annotation, eg: `Foo{internal_name: CONST: u32}`,
+unless that name is also an [`alias`](#alias-attr) of the field.
+Renamed fields can be destructured by their new name with a string literal,
+eg: `Foo{"foo": CONST}`
+(see [string-literal field names](crate::multiconst#string-literal-field-names)).
+
+[example that uses this attribute](#rename-example)
+
+<span id = "alias-attr"></span>
+### `#[field_type(alias = "foo")]`
+
+Generates an additional impl of [`FieldType`] for the field, with `foo` as the field name.
+The name has the same syntax as the name in [`rename`](#rename-attr),
+and this attribute can be used multiple times on a field.
+
+Aliases are not included in [`FieldList`].
+
+The names of the fields, their aliases, and flattened fields
+must be different from each other in a struct or enum variant,
+the derive errors otherwise.
+
+[example that uses this attribute](#rename-example)
+
+<span id = "flatten-attr"></span>
+### `#[field_type(flatten(foo, bar, 0))]`
+
+Generates impls of [`FieldType`] for the listed fields of the field's type,
+so that they can be queried from the deriving type,
+eg: `#[field_type(flatten(port))] server: Server` makes
+`GetFieldType<Self, field_name!(port)>` be `GetFieldType<Server, field_name!(port)>`.
+
+The fields must be listed because the derive can't see the definition of the field's type.
+The generated impls require the field's type to implement [`FieldType`] for those fields.
+
+The [`multiconst`](crate::multiconst) macros destructure flattened fields
+by their name in a string literal, eg: `Config{"port": PORT, ..}`
+(see [string-literal field names](crate::multiconst#string-literal-field-names)),
+or through the field that contains them, eg: `Config{server: Server{port: PORT, ..}, ..}`.
+
+The flattened fields are read with the methods that the derive generates
+for the field's type, so unless the field's type is a tuple, an array,
+or a type parameter, it must also derive [`FieldType`].
+Flattened fields of type parameters can't be destructured by name.
+
+The derive doesn't generate these methods for `#[repr(packed)]` structs,
+because they'd return references to fields that can be unaligned,
+so the fields of those structs can't be destructured with string-literal names.
+
+[example that uses this attribute](#flatten-example)
+
 # Examples
 
 ### Basic
@@ -177,6 +260,109 @@ pub struct Flags {
 
 ```
 
+<span id = "enum-example"></span>
+### Enum
+
+This example demonstrates querying the types of the fields of an enum.
+
+```rust
+use multiconst::{field_name, FieldType, GetFieldType, VariantField};
+
+type RadiusField = VariantField<field_name!(Circle), field_name!(radius)>;
+type LineEndField = VariantField<field_name!(Line), field_name!(1)>;
+
+let _: GetFieldType<Shape, RadiusField> = 3.0f32;
+let _: GetFieldType<Shape, LineEndField> = (8u32, 13u32);
+
+#[derive(FieldType)]
+enum Shape {
+    Circle { radius: f32 },
+    Line((u32, u32), (u32, u32)),
+}
+
+```
+
+<span id = "rename-example"></span>
+### Renamed fields
+
+This example demonstrates the `#[field_type(rename = "...")]` and
+`#[field_type(alias = "...")]` attributes.
+
+```rust
+use multiconst::{field_name, multiconst, FieldType, GetFieldType};
+
+let _: GetFieldType<Token, field_name!(kind)> = 'a';
+let _: GetFieldType<Token, field_name!(text)> = "hello";
+
+multiconst!{
+    const Token{"kind": KIND, "text": TEXT}: Token = Token {
+        r#type: 'b',
+        internal_text: "world",
+    };
+}
+
+assert_eq!(KIND, 'b');
+assert_eq!(TEXT, "world");
+
+// the fields can also be destructured by their Rust names,
+// renamed fields need a type annotation, since they're not queryable by that name.
+multiconst!{
+    const Token{r#type: KIND2, internal_text: TEXT2: &str}: Token = Token {
+        r#type: 'c',
+        internal_text: "foo",
+    };
+}
+
+assert_eq!(KIND2, 'c');
+assert_eq!(TEXT2, "foo");
+
+
+#[derive(FieldType)]
+struct Token {
+    #[field_type(alias = "kind")]
+    r#type: char,
+    #[field_type(rename = "text")]
+    internal_text: &'static str,
+}
+
+```
+
+<span id = "flatten-example"></span>
+### Flattened fields
+
+This example demonstrates the `#[field_type(flatten(...))]` attribute.
+
+```rust
+use multiconst::{field_name, multiconst, FieldType, GetFieldType};
+
+let _: GetFieldType<Config, field_name!(port)> = 8080u16;
+
+multiconst!{
+    const Config{"host": HOST, server: Server{port: PORT, ..}, ..}: Config = Config {
+        name: "local",
+        server: Server{host: "localhost", port: 3000},
+    };
+}
+
+assert_eq!(HOST, "localhost");
+assert_eq!(PORT, 3000);
+
+
+#[derive(FieldType)]
+struct Config {
+    name: &'static str,
+    #[field_type(flatten(host, port))]
+    server: Server,
+}
+
+#[derive(FieldType)]
+struct Server {
+    host: &'static str,
+    port: u16,
+}
+
+```
+
 <span id = "crate-attr-example"></span>
 ### Reexport example
 
@@ -207,6 +393,8 @@ struct Foo(u32, u32);
 
 
 [`FieldType`]: trait@crate::FieldType
+[`VariantField`]: crate::VariantField
+[`FieldList`]: crate::FieldList
 */
 #[cfg(feature = "derive")]
 pub use multiconst_proc_macros::FieldType;
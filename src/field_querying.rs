@@ -1,13 +1,20 @@
 //! Types and traits for querying the types of fields/elements.
 
+mod field_list;
 mod field_name;
+#[cfg(feature = "rust_1_78")]
+mod field_name_str;
 mod field_type;
 mod field_type_prim_impls;
 mod field_type_struct_impls;
 
 pub use self::{
-    field_name::{TChars, TIdent, Usize},
+    field_list::FieldList,
+    field_name::{FieldPath, TChars, TIdent, Usize, VariantField},
     field_type::{FieldType, GetFieldType},
 };
 
+#[cfg(feature = "rust_1_78")]
+pub use self::field_name_str::FieldNameStr;
+
 ///////////////////////////////////////////////////////////////////////////
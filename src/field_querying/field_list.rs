@@ -0,0 +1,52 @@
+/// The list of fields in a type that can be queried with [`FieldType`].
+///
+/// The [`FieldType`](derive@crate::FieldType) derive implements this trait
+/// for structs and unions, listing the fields that it implements [`FieldType`] for,
+/// in declaration order.
+///
+/// # Example
+///
+/// This example demonstrates iterating over the fields of a struct.
+///
+#[cfg_attr(feature = "derive", doc = "```rust")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use multiconst::{field_name, FieldList, FieldType};
+///
+/// use static_assertions::assert_type_eq_all;
+///
+/// assert_eq!(Point::FIELD_NAMES, ["x", "y", "z"]);
+/// assert_eq!(Point::FIELD_COUNT, 3);
+///
+/// assert_eq!(Pair::FIELD_NAMES, ["0", "1"]);
+///
+/// assert_type_eq_all!(
+///     <Point as FieldList>::Names,
+///     (field_name!(x), field_name!(y), field_name!(z)),
+/// );
+/// assert_type_eq_all!(<Pair as FieldList>::Names, (field_name!(0), field_name!(1)));
+///
+/// #[derive(FieldType)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+///     z: u32,
+/// }
+///
+/// #[derive(FieldType)]
+/// struct Pair(u8, u16);
+///
+/// ```
+///
+/// [`FieldType`]: trait@crate::FieldType
+pub trait FieldList {
+    /// A tuple of the names of the fields, each name being what
+    /// [`field_name`](crate::field_name) expands to for that field.
+    type Names;
+
+    /// The names of the fields, eg: `["foo", "bar"]` for braced structs,
+    /// `["0", "1"]` for tuple structs.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// The amount of fields.
+    const FIELD_COUNT: usize = Self::FIELD_NAMES.len();
+}
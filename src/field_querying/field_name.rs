@@ -102,3 +102,83 @@ pub struct TChars<
 ///
 ///
 pub struct Usize<const N: usize>;
+
+/// Type-level name of a field in an enum variant,
+/// used to query the type of the fields of enums.
+///
+/// `V` is the name of the variant, and `F` is the name of the field,
+/// both of them being what [`field_name`] expands to.
+///
+/// # Example
+///
+/// ### `FieldType` implementation
+///
+/// This example demonstrates how the fields of enums are queried.
+///
+/// ```rust
+/// use multiconst::{field_name, FieldType, GetFieldType, VariantField};
+///
+/// let _: GetFieldType<Shape, VariantField<field_name!(Circle), field_name!(radius)>> = 3.0f32;
+/// let _: GetFieldType<Shape, VariantField<field_name!(Line), field_name!(1)>> = (3u32, 5u32);
+///
+/// enum Shape {
+///     Circle { radius: f32 },
+///     Line((u32, u32), (u32, u32)),
+/// }
+///
+/// impl FieldType<VariantField<field_name!(Circle), field_name!(radius)>> for Shape {
+///     type Type = f32;
+/// }
+///
+/// impl FieldType<VariantField<field_name!(Line), field_name!(0)>> for Shape {
+///     type Type = (u32, u32);
+/// }
+///
+/// impl FieldType<VariantField<field_name!(Line), field_name!(1)>> for Shape {
+///     type Type = (u32, u32);
+/// }
+///
+/// ```
+///
+/// [`field_name`]: crate::field_name
+pub struct VariantField<V, F>(core::marker::PhantomData<(V, F)>);
+
+impl<V, F> VariantField<V, F> {
+    /// Constructs a `VariantField`
+    pub const NEW: Self = Self(core::marker::PhantomData);
+}
+
+/// Type-level path to a nested field, as a list of field names,
+/// used to query the type of nested fields.
+///
+/// `Name` is the name of the first field in the path,
+/// and `Rest` is the path to the nested field inside of it,
+/// with `()` being the empty path.
+///
+/// This is what [`field_path`] expands to, eg: `field_path!(foo.0)` expands to
+/// `FieldPath<field_name!(foo), FieldPath<field_name!(0), ()>>`.
+///
+/// Paths can be arbitrarily deep,
+/// unlike tuples of field names, which can have up to 16 names.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::{field_name, field_path, FieldPath, GetFieldType};
+///
+/// type Foo = ([u32; 2], (u64, &'static str));
+///
+/// type Path = FieldPath<field_name!(1), FieldPath<field_name!(1), ()>>;
+///
+/// let _: GetFieldType<Foo, Path> = "hello";
+/// let _: GetFieldType<Foo, field_path!(1.1)> = "hello";
+///
+/// ```
+///
+/// [`field_path`]: crate::field_path
+pub struct FieldPath<Name, Rest>(core::marker::PhantomData<(Name, Rest)>);
+
+impl<Name, Rest> FieldPath<Name, Rest> {
+    /// Constructs a `FieldPath`
+    pub const NEW: Self = Self(core::marker::PhantomData);
+}
@@ -0,0 +1,186 @@
+use crate::{TChars, TIdent, Usize};
+
+use core::marker::PhantomData;
+
+/// Gets the name that a type-level field name represents, as a `&'static str`.
+///
+/// This is implemented for the [`TIdent`]s (with up to 8 [`TChars`])
+/// and [`Usize`]s that [`field_name`] expands to,
+/// the space padding of [`TChars`] is not included in the name.
+///
+/// # Example
+///
+/// This example demonstrates how generic code can describe a field in messages.
+///
+/// ```rust
+/// use multiconst::{field_name, FieldNameStr, FieldType};
+///
+/// use std::any::type_name;
+///
+/// assert_eq!(describe_field::<Foo, field_name!(length)>(), "field `length` of `Foo`");
+/// assert_eq!(describe_field::<Bar, field_name!(0)>(), "field `0` of `Bar`");
+///
+/// fn describe_field<T, N>() -> String
+/// where
+///     T: FieldType<N>,
+///     N: FieldNameStr,
+/// {
+///     let type_name = type_name::<T>().rsplit("::").next().unwrap();
+///     format!("field `{}` of `{}`", N::NAME, type_name)
+/// }
+///
+/// struct Foo {
+///     length: u32,
+/// }
+///
+/// impl FieldType<field_name!(length)> for Foo {
+///     type Type = u32;
+/// }
+///
+/// struct Bar(u64);
+///
+/// impl FieldType<field_name!(0)> for Bar {
+///     type Type = u64;
+/// }
+/// ```
+///
+/// [`field_name`]: crate::field_name
+pub trait FieldNameStr {
+    /// The name of the field, eg: `"foo"` for `field_name!(foo)`,
+    /// `"3"` for `field_name!(3)`.
+    const NAME: &'static str;
+}
+
+impl<const N: usize> FieldNameStr for Usize<N> {
+    const NAME: &'static str = utf8_prefix_to_str(NameBytes::<Self>::BYTES);
+}
+
+impl<const N: usize> NameBytes<Usize<N>> {
+    // 20 is the amount of digits in `u64::MAX`
+    const BYTES: &'static [u8] = &{
+        let mut digits = [0u8; 20];
+        let mut len = 0;
+        let mut n = N;
+        loop {
+            digits[len] = b'0' + (n % 10) as u8;
+            len += 1;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+
+        let mut out = [0u8; 20];
+        let mut i = 0;
+        while i < len {
+            out[i] = digits[len - 1 - i];
+            i += 1;
+        }
+        out
+    };
+}
+
+/// Implemented by [`TChars`], for getting its `char` arguments.
+pub trait CharChunk {
+    const CHARS: [char; 8];
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+    > CharChunk for TChars<C0, C1, C2, C3, C4, C5, C6, C7>
+{
+    const CHARS: [char; 8] = [C0, C1, C2, C3, C4, C5, C6, C7];
+}
+
+// Holds the UTF-8 encoded name of `T` in a `BYTES` associated constant,
+// a separate constant is required for the bytes to be `'static`.
+struct NameBytes<T>(PhantomData<T>);
+
+macro_rules! impl_tident_name {
+    ($( ($capacity:literal: $($chunk:ident)*) )*) => {
+        $(
+            impl<$($chunk: CharChunk,)*> FieldNameStr for TIdent<($($chunk,)*)> {
+                const NAME: &'static str = utf8_prefix_to_str(NameBytes::<Self>::BYTES);
+            }
+
+            impl<$($chunk: CharChunk,)*> NameBytes<TIdent<($($chunk,)*)>> {
+                const BYTES: &'static [u8] =
+                    &encode_chunks::<$capacity>(&[$($chunk::CHARS,)*]);
+            }
+        )*
+    };
+}
+
+// the capacity is 4 bytes (the maximum UTF-8 length of a `char`) per char
+impl_tident_name! {
+    (32: C0)
+    (64: C0 C1)
+    (96: C0 C1 C2)
+    (128: C0 C1 C2 C3)
+    (160: C0 C1 C2 C3 C4)
+    (192: C0 C1 C2 C3 C4 C5)
+    (224: C0 C1 C2 C3 C4 C5 C6)
+    (256: C0 C1 C2 C3 C4 C5 C6 C7)
+}
+
+/// Encodes the chars in `chunks` as UTF-8, skipping the padding spaces,
+/// and filling the rest of the array with zeros.
+const fn encode_chunks<const CAP: usize>(chunks: &[[char; 8]]) -> [u8; CAP] {
+    let mut out = [0u8; CAP];
+    let mut len = 0;
+
+    let mut ci = 0;
+    while ci < chunks.len() {
+        let mut i = 0;
+        while i < 8 {
+            let c = chunks[ci][i] as u32;
+            i += 1;
+
+            if c == ' ' as u32 {
+                continue;
+            } else if c < 0x80 {
+                out[len] = c as u8;
+                len += 1;
+            } else if c < 0x800 {
+                out[len] = 0xC0 | (c >> 6) as u8;
+                out[len + 1] = 0x80 | (c & 0x3F) as u8;
+                len += 2;
+            } else if c < 0x10000 {
+                out[len] = 0xE0 | (c >> 12) as u8;
+                out[len + 1] = 0x80 | ((c >> 6) & 0x3F) as u8;
+                out[len + 2] = 0x80 | (c & 0x3F) as u8;
+                len += 3;
+            } else {
+                out[len] = 0xF0 | (c >> 18) as u8;
+                out[len + 1] = 0x80 | ((c >> 12) & 0x3F) as u8;
+                out[len + 2] = 0x80 | ((c >> 6) & 0x3F) as u8;
+                out[len + 3] = 0x80 | (c & 0x3F) as u8;
+                len += 4;
+            }
+        }
+        ci += 1;
+    }
+
+    out
+}
+
+/// Converts the bytes before the first zero into a `&str`.
+#[allow(clippy::incompatible_msrv)]
+const fn utf8_prefix_to_str(bytes: &'static [u8]) -> &'static str {
+    let mut len = 0;
+    while len < bytes.len() && bytes[len] != 0 {
+        len += 1;
+    }
+
+    match core::str::from_utf8(bytes.split_at(len).0) {
+        Ok(x) => x,
+        Err(_) => panic!("field names are valid UTF-8"),
+    }
+}
@@ -2,7 +2,13 @@
 ///
 /// The name of the field is represented with the `Name` type parameter
 ///
-/// The type of nested fields can be queried by passing a tuple of field names.
+/// The type of nested fields can be queried by passing a
+/// [`FieldPath`](crate::FieldPath) (which [`field_path`](crate::field_path) expands to),
+/// or a tuple of up to 16 field names.
+///
+/// References (`&T` and `&mut T`) and [`ManuallyDrop<T>`](core::mem::ManuallyDrop)
+/// implement this trait for the same field names as `T`,
+/// which allows nested field paths to go through fields of those types.
 ///
 /// You can derive this tarit with the [`FieldType`](derive@crate::FieldType)
 /// derive (requires the "derive" feature).
@@ -47,6 +53,29 @@
 ///
 /// ```
 ///
+/// <span id = "checked-index-example"></span>
+/// ### Checked array index
+///
+/// This example demonstrates how querying an out-of-bounds array element
+/// can be made to error at compile-time.
+///
+/// ```compile_fail
+/// use multiconst::{FieldType, Usize};
+///
+/// type Arr = [u8; 2];
+///
+/// // `GetFieldType<Arr, Usize<5>>` is `u8`, this constant errors because `5 >= 2`.
+/// const _: () = <Arr as FieldType<Usize<5>>>::ASSERT_EXISTS;
+/// ```
+///
+/// ```rust
+/// use multiconst::{FieldType, Usize};
+///
+/// type Arr = [u8; 2];
+///
+/// const _: () = <Arr as FieldType<Usize<1>>>::ASSERT_EXISTS;
+/// ```
+///
 /// ### Derived
 ///
 /// Examples of deriving this trait with the [`FieldType`](derive@crate::FieldType)
@@ -55,14 +84,44 @@
 ///
 ///
 ///
+#[cfg_attr(
+    feature = "rust_1_78",
+    diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't have a destructurable `{Names}`",
+        label = "`{Self}` doesn't implement `FieldType` for this field name",
+        note = "derive `FieldType` for `{Self}`, or annotate the type of the field \
+                in the pattern, eg: `Foo{{bar: BAR: u32, ..}}`",
+        note = "`TField<foo>` is the `foo` field in a `multiconst` pattern, \
+                `TIdent`s of `TChars` spell field names in 8-`char` chunks padded with spaces, \
+                and positional fields are `Usize`s"
+    )
+)]
 pub trait FieldType<Names> {
     /// The type of the field.
     type Type;
+
+    /// Evaluating this constant causes a compile-time error if the field doesn't exist.
+    ///
+    /// This is for impls that can't check that the field exists with trait bounds,
+    /// eg: `[T; N]` implements `FieldType<Usize<I>>` for all `I`,
+    /// and evaluating this constant errors if `I >= N`.
+    ///
+    /// The [`multiconst`](crate::multiconst) macro evaluates this constant
+    /// for the last element that each array pattern requires,
+    /// when the array type isn't written as an array (eg: a type alias),
+    /// so that arrays shorter than the pattern error with a clear message.
+    /// It doesn't evaluate this constant inside impls,
+    /// where the array pattern itself errors for arrays that are too short.
+    ///
+    /// [example](#checked-index-example)
+    const ASSERT_EXISTS: () = ();
 }
 
 /// Gets the type of a (potentially nested) field.
 ///
-/// The type of nested fields can be queried by passing a tuple of field names.
+/// The type of nested fields can be queried by passing a
+/// [`FieldPath`](crate::FieldPath) (which [`field_path`](crate::field_path) expands to),
+/// or a tuple of up to 16 field names.
 ///
 /// # Examples
 ///
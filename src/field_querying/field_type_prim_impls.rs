@@ -1,87 +1,113 @@
-use crate::{FieldType, Usize};
+use crate::{FieldPath, FieldType, GetFieldType, TIdent, Usize, VariantField};
 
-use crate::utils_for_macros::SeqLength;
+use core::mem::ManuallyDrop;
 
-macro_rules! impl_nested_field_type {
-    (
-        $((
-            $(($bounded_ty:ident $name:ident $field_ty:ident))*,
-            $last_field_ty:ident
-        ))*
-    ) => {
-        $(
-            impl<$($bounded_ty, $name,)* $last_field_ty>
-                FieldType<($($name,)*)>
-            for T0
-            where
-                $($bounded_ty: FieldType<$name, Type = $field_ty>,)*
-            {
-                type Type = $last_field_ty;
-            }
-        )*
-    };
-}
+use crate::utils_for_macros::{FieldMarker, SeqLength, TField};
 
 impl<T> FieldType<()> for T {
     type Type = T;
 }
 
-/*
-fn main(){
-    let per_line = 6;
-    for len in 1..=8 {
-        print!("(");
-        for i in 0..len {
-            if i % per_line == 0 && len >= per_line {
-                print!("\n    ");
-            }
-            print!("(T{0} N{0} T{1}) ", i, i + 1);
-        }
-        print!(", ");
-        if len >= per_line { print!("\n    ") }
-        print!("T{}", len);
-        if len >= per_line { print!("\n") }
-        println!(")");
-    }
+impl<T, N, R> FieldType<FieldPath<N, R>> for T
+where
+    T: FieldType<N>,
+    GetFieldType<T, N>: FieldType<R>,
+{
+    type Type = GetFieldType<GetFieldType<T, N>, R>;
+
+    const ASSERT_EXISTS: () = {
+        let () = <T as FieldType<N>>::ASSERT_EXISTS;
+        <GetFieldType<T, N> as FieldType<R>>::ASSERT_EXISTS
+    };
 }
 
-*/
+// `do_not_recommend` makes errors mention `TField<M>` instead of the name of the field
+#[cfg_attr(
+    feature = "rust_1_78",
+    allow(unknown_or_malformed_diagnostic_attributes),
+    diagnostic::do_not_recommend
+)]
+impl<T, M> FieldType<TField<M>> for T
+where
+    M: FieldMarker,
+    T: FieldType<M::Name>,
+{
+    type Type = GetFieldType<T, M::Name>;
 
-impl_nested_field_type! {
-    ((T0 N0 T1) , T1)
-    ((T0 N0 T1) (T1 N1 T2) , T2)
-    ((T0 N0 T1) (T1 N1 T2) (T2 N2 T3) , T3)
-    ((T0 N0 T1) (T1 N1 T2) (T2 N2 T3) (T3 N3 T4) , T4)
-    ((T0 N0 T1) (T1 N1 T2) (T2 N2 T3) (T3 N3 T4) (T4 N4 T5) , T5)
-    (
-        (T0 N0 T1) (T1 N1 T2) (T2 N2 T3) (T3 N3 T4) (T4 N4 T5) (T5 N5 T6) ,
-        T6
-    )
-    (
-        (T0 N0 T1) (T1 N1 T2) (T2 N2 T3) (T3 N3 T4) (T4 N4 T5) (T5 N5 T6)
-        (T6 N6 T7) ,
-        T7
-    )
-    (
-        (T0 N0 T1) (T1 N1 T2) (T2 N2 T3) (T3 N3 T4) (T4 N4 T5) (T5 N5 T6)
-        (T6 N6 T7) (T7 N7 T8) ,
-        T8
-    )
+    const ASSERT_EXISTS: () = <T as FieldType<M::Name>>::ASSERT_EXISTS;
+}
+
+// tuples of field names are equivalent to `FieldPath` lists of the same names
+macro_rules! impl_tuple_path_field_type {
+    (@cons) => { () };
+    (@cons $first:ident $($rest:ident)*) => {
+        FieldPath<$first, impl_tuple_path_field_type!(@cons $($rest)*)>
+    };
+    ($( ($($name:ident)*) )*) => {
+        $(
+            impl<T, $($name,)*> FieldType<($($name,)*)> for T
+            where
+                T: FieldType<impl_tuple_path_field_type!(@cons $($name)*)>,
+            {
+                type Type = GetFieldType<T, impl_tuple_path_field_type!(@cons $($name)*)>;
+
+                const ASSERT_EXISTS: () =
+                    <T as FieldType<impl_tuple_path_field_type!(@cons $($name)*)>>::ASSERT_EXISTS;
+            }
+        )*
+    };
+}
+
+impl_tuple_path_field_type! {
+    (N0)
+    (N0 N1)
+    (N0 N1 N2)
+    (N0 N1 N2 N3)
+    (N0 N1 N2 N3 N4)
+    (N0 N1 N2 N3 N4 N5)
+    (N0 N1 N2 N3 N4 N5 N6)
+    (N0 N1 N2 N3 N4 N5 N6 N7)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10 N11)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10 N11 N12)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10 N11 N12 N13)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10 N11 N12 N13 N14)
+    (N0 N1 N2 N3 N4 N5 N6 N7 N8 N9 N10 N11 N12 N13 N14 N15)
 }
 
 /////////////////////////////////////////////////////////////////////////////
 //                     FieldType impls
 
-/// Does not check that `I` is inside the array.
+/// Does not check that `I` is inside the array,
+/// evaluating `ASSERT_EXISTS` does.
 impl<T, const I: usize, const N: usize> FieldType<Usize<I>> for [T; N] {
     type Type = T;
+
+    const ASSERT_EXISTS: () = assert_index_in_bounds(I, N);
+}
+
+#[cfg(feature = "rust_1_61")]
+#[allow(clippy::incompatible_msrv)]
+const fn assert_index_in_bounds(index: usize, len: usize) {
+    if index >= len {
+        panic!("the `Usize<I>` field of `[T; N]` doesn't exist, because `I >= N`")
+    }
+}
+
+// errors with an "index out of bounds" message,
+// because panicking in const fns requires Rust 1.57.0
+#[cfg(not(feature = "rust_1_61"))]
+const fn assert_index_in_bounds(index: usize, len: usize) {
+    [()][(index >= len) as usize]
 }
 
 /*
 
 fn main(){
     let per_line = 8;
-    for len in 1..=8 {
+    for len in 1..=16 {
         print!("(");
         for i in 0..len {
             if i % per_line == 0 && len >= per_line {
@@ -155,4 +181,75 @@ tuple_impls! {
         (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7) ,
         (T0,T1,T2,T3,T4,T5,T6,T7,), 8
     )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,), 9
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,), 10
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,), 11
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) (T11 11) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,T11,), 12
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) (T11 11) (T12 12) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,T11,T12,), 13
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) (T11 11) (T12 12) (T13 13) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,T11,T12,T13,), 14
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) (T11 11) (T12 12) (T13 13) (T14 14) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,T11,T12,T13,T14,), 15
+    )
+    (
+        (T0 0) (T1 1) (T2 2) (T3 3) (T4 4) (T5 5) (T6 6) (T7 7)
+        (T8 8) (T9 9) (T10 10) (T11 11) (T12 12) (T13 13) (T14 14) (T15 15) ,
+        (T0,T1,T2,T3,T4,T5,T6,T7,T8,T9,T10,T11,T12,T13,T14,T15,), 16
+    )
+}
+
+/////////////////////////////////////////////////////////////////////////////
+//                     Forwarding impls
+
+// Forwards `FieldType` impls through references and transparent wrappers,
+// the impls are per kind of field name to not overlap with the path impls.
+macro_rules! forwarding_impls {
+    ($( ($($name_params:tt)*) $name:ty ),* $(,)?) => {
+        $(
+            forwarding_impls!{@impl ('a, T: ?Sized, $($name_params)*) &'a T, $name}
+            forwarding_impls!{@impl ('a, T: ?Sized, $($name_params)*) &'a mut T, $name}
+            forwarding_impls!{@impl (T, $($name_params)*) ManuallyDrop<T>, $name}
+        )*
+    };
+    (@impl ($($params:tt)*) $wrapper:ty, $name:ty) => {
+        impl<$($params)*> FieldType<$name> for $wrapper
+        where
+            T: FieldType<$name>,
+        {
+            type Type = GetFieldType<T, $name>;
+
+            const ASSERT_EXISTS: () = <T as FieldType<$name>>::ASSERT_EXISTS;
+        }
+    };
+}
+
+forwarding_impls! {
+    (C) TIdent<C>,
+    (const I: usize) Usize<I>,
+    (V, F) VariantField<V, F>,
 }
@@ -87,6 +87,18 @@
 //!
 //! - `"derive"`: enables the [`FieldType`][FieldType-derive] derive macro.
 //!
+//! - `"rust_1_61"`: enables the [`seq`] module, with generators of integer sequences,
+//! and allows [destructuring slices][slice-destructuring] in [`multiconst`].
+//!
+//! - `"rust_1_64"`: allows [coercing byte array bindings to `&'static str`][str-coercion]
+//! in [`multiconst`].
+//! Enables the `"rust_1_61"` feature.
+//!
+//! - `"rust_1_78"`: improves the error messages for fields that can't be queried with
+//! [`FieldType`][FieldType-trait], eg: destructuring a struct that doesn't implement it.
+//! Enables the [`FieldNameStr`] trait, for getting the names of fields as strings.
+//! Enables the `"rust_1_64"` feature.
+//!
 //!
 //! # No-std support
 //!
@@ -102,6 +114,10 @@
 //! [FieldType-derive]: derive@crate::FieldType
 //! [multiconst-examples]: crate::multiconst#examples
 //! [example-struct-ty-annot]: crate::multiconst#example-struct-ty-annot
+//! [str-coercion]: crate::multiconst#str-coercion-example
+//! [slice-destructuring]: crate::multiconst#slice-destructuring-example
+//! [`seq`]: crate::seq
+//! [`FieldNameStr`]: crate::FieldNameStr
 #![cfg_attr(feature = "docsrs", feature(doc_auto_cfg))]
 #![no_std]
 #![forbid(unsafe_code)]
@@ -115,17 +131,36 @@ include! {"derive_macro_reexport.rs"}
 mod macros;
 mod utils_for_macros;
 
+#[cfg(feature = "rust_1_61")]
+pub mod seq;
+
 #[doc(hidden)]
 pub mod __ {
     pub use multiconst_proc_macros::{
-        __priv_associated_multiconst_proc_macro, __priv_field_name_aliases_proc_macro,
-        __priv_field_proc_macro, __priv_multiconst_proc_macro,
+        __priv_associated_multiconst_proc_macro, __priv_extension_multiconst_proc_macro,
+        __priv_field_name_aliases_proc_macro, __priv_field_path_proc_macro,
+        __priv_field_proc_macro, __priv_multiconst_enum_proc_macro, __priv_multiconst_proc_macro,
     };
 
     pub use crate::{
-        field_querying::{GetFieldType, TChars, TIdent, Usize},
-        utils_for_macros::{AssertSameTypes, SeqLength, Type},
+        __priv_bytes_to_str as bytes_to_str, __priv_copy_slice_element as copy_slice_element,
+        __priv_if_rust_1_61 as if_rust_1_61, __priv_slice_length_mismatch as slice_length_mismatch,
+        field_querying::{FieldPath, FieldType, GetFieldType, TChars, TIdent, Usize},
+        utils_for_macros::{
+            array_rem_length, AssertSameTypes, FieldAccessor, FieldMarker, SeqLength, TField, Type,
+        },
     };
 
-    pub use core::{compile_error, ops::Range, primitive::usize};
+    #[cfg(feature = "rust_1_61")]
+    pub use crate::utils_for_macros::{copy_slice_element, slice_length_mismatch};
+
+    #[cfg(feature = "rust_1_64")]
+    pub use crate::utils_for_macros::bytes_to_str;
+
+    pub use core::{
+        compile_error,
+        ops::Range,
+        option::Option,
+        primitive::{str, usize},
+    };
 }
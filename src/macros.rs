@@ -7,6 +7,12 @@ mod multiconst_macro;
 #[macro_use]
 mod associated_multiconst_macro;
 
+#[macro_use]
+mod extension_multiconst_macro;
+
+#[macro_use]
+mod multiconst_enum_macro;
+
 /// For loop over a range
 ///
 /// # Example
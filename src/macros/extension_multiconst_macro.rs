@@ -0,0 +1,68 @@
+/// Destructures a constant expression into multiple associated constants
+/// of an extension trait, which this macro declares and implements for a type.
+///
+/// This allows declaring destructured associated constants for types
+/// that can't have inherent impls in the crate, like `u32` or types from other crates.
+///
+/// This macro takes the same constants as [`multiconst`],
+/// wrapped in `<attributes> <visibility> trait <Name> for <Type> { ... }`.
+/// The trait has the declarations of the constants (with their documentation),
+/// and the impl of the trait for the type has their values,
+/// which are all evaluated from one expression per `const` item.
+///
+/// The constants can't have visibility qualifiers, nor be in modules.
+///
+/// # Examples
+///
+/// ### Integer limits
+///
+/// ```rust
+/// use multiconst::extension_multiconst;
+///
+/// extension_multiconst!{
+///     /// The range of valid percentages.
+///     pub trait U32Limits for u32 {
+///         #[multiconst(registry = LIMITS)]
+///         const (
+///             /// The smallest valid percentage
+///             LO,
+///             /// The largest valid percentage
+///             HI,
+///         ): (u32, u32) = (0, 100);
+///     }
+/// }
+///
+/// assert_eq!(u32::LO, 0);
+/// assert_eq!(u32::HI, 100);
+/// assert_eq!(<u32 as U32Limits>::LIMITS, [("LO", 0), ("HI", 100)]);
+///
+/// ```
+///
+/// ### Foreign type
+///
+/// ```rust
+/// use multiconst::extension_multiconst;
+///
+/// use std::mem::{align_of, size_of};
+///
+/// extension_multiconst!{
+///     trait Layout for Vec<u8> {
+///         const [SIZE, ALIGN]: [usize; 2] = [size_of::<Self>(), align_of::<Self>()];
+///     }
+/// }
+///
+/// assert_eq!(Vec::<u8>::SIZE, size_of::<Vec<u8>>());
+/// assert_eq!(Vec::<u8>::ALIGN, align_of::<Vec<u8>>());
+///
+/// ```
+///
+#[macro_export]
+macro_rules! extension_multiconst {
+    ($($args:tt)*) => {
+        $crate::__::__priv_extension_multiconst_proc_macro!{
+            $crate
+
+            $($args)*
+        }
+    };
+}
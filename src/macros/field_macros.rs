@@ -48,7 +48,52 @@ macro_rules! field_name {
     };
 }
 
-/// Declares type aliases for type-level representations of field names.
+/// Macro that expands to a type-level representation of a path to a nested field,
+/// eg: `field_path!(foo.bar.0)`.
+///
+/// This macro can be passed as the `Names` argument of
+/// [`FieldType`] and [`GetFieldType`], to query the type of a nested field.
+///
+/// This expands to a [`FieldPath`] list of what [`field_name`] expands to
+/// for each field name in the path, eg: `field_path!(foo.0)` expands to
+/// `FieldPath<field_name!(foo), FieldPath<field_name!(0), ()>>`.
+///
+/// # Examples
+///
+/// ### Querying a nested field's type
+///
+/// ```rust
+/// use multiconst::{GetFieldType, field_path};
+///
+/// type Foo = (std::ops::Range<u8>, [(u16, &'static str); 2]);
+///
+/// let _: GetFieldType<Foo, field_path!(0.start)> = 3u8;
+/// let _: GetFieldType<Foo, field_path!(1.0.1)> = "hello";
+///
+/// ```
+///
+/// [`FieldType`]: crate::FieldType
+/// [`GetFieldType`]: crate::GetFieldType
+/// [`field_name`]: crate::field_name
+/// [`FieldPath`]: crate::FieldPath
+///
+#[macro_export]
+macro_rules! field_path {
+    ($($args:tt)*) => {
+        $crate::__::__priv_field_path_proc_macro!{
+            $crate
+
+            $($args)*
+        }
+    };
+}
+
+/// Declares type aliases for type-level representations of field names,
+/// or of paths to nested fields.
+///
+/// An alias for a path to a nested field is declared with `.`-separated field names,
+/// eg: `pub FooBar = foo.bar`, which is equivalent to
+/// `pub type FooBar = field_path!(foo.bar);`.
 ///
 /// The aliases can be passed as the `Names` argument of
 /// [`FieldType`] and [`GetFieldType`], as the name of the field.
@@ -73,6 +118,9 @@ macro_rules! field_name {
 ///
 ///         // equivalent to `pub type End = field_name!(end);`
 ///         pub End = end,
+///
+///         // equivalent to `pub type EndThird = field_path!(end.2);`
+///         pub EndThird = end.2,
 ///     }
 /// }
 ///
@@ -84,6 +132,12 @@ macro_rules! field_name {
 /// }
 ///
 /// {
+///     type RA = std::ops::Range<[u8; 4]>;
+///
+///     let _: GetFieldType<RA, names::EndThird> = 3u8;
+/// }
+///
+/// {
 ///     type TUP = (&'static str, Option<u8>);
 ///
 ///     let _: GetFieldType<TUP, names::F0> = "hello";
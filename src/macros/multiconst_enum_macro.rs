@@ -0,0 +1,97 @@
+/// Declares a fieldless enum whose discriminants come from a constant array.
+///
+/// # Syntax
+///
+/// This uses a macro_rules-like syntax to describe the parameters.
+///
+/// ```text
+/// $(
+///     $(#[$attr:meta])*
+///     $vis:vis enum $name:ident {
+///         $(
+///             $(#[$vattr:meta])*
+///             $variant:ident
+///         ),*
+///         $(,)?
+///     }: [$repr:ty; $length:expr] = $value:expr;
+/// )*
+/// ```
+///
+/// `$length` can be `_` to infer the length from the amount of variants,
+/// otherwise it must be the amount of variants.
+///
+/// `$repr` is the type of the discriminants,
+/// it must be the same as the `#[repr(...)]` type of the enum
+/// (`isize` if the enum doesn't have a `#[repr(...)]` attribute).
+///
+/// The `n`th variant uses the `n`th element of `$value` as its discriminant.
+///
+/// # Generated code
+///
+/// Aside from the enum, this macro generates these inherent items,
+/// using the visibility of the enum:
+///
+/// - `const ALL: [Self; N]`: all the variants of the enum, in declaration order.
+///
+/// - `const fn from_repr(repr: $repr) -> Option<Self>`:
+///   gets the variant whose discriminant is `repr`.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::multiconst_enum;
+///
+/// multiconst_enum! {
+///     #[repr(u8)]
+///     #[derive(Debug, Copy, Clone, PartialEq)]
+///     pub enum Op {
+///         Nop,
+///         Load,
+///         /// Stores stuff
+///         Store,
+///     }: [u8; _] = opcodes(0x10);
+/// }
+///
+/// assert_eq!(Op::Nop as u8, 0x10);
+/// assert_eq!(Op::Load as u8, 0x12);
+/// assert_eq!(Op::Store as u8, 0x14);
+///
+/// assert_eq!(Op::ALL, [Op::Nop, Op::Load, Op::Store]);
+///
+/// assert_eq!(Op::from_repr(0x10), Some(Op::Nop));
+/// assert_eq!(Op::from_repr(0x11), None);
+/// assert_eq!(Op::from_repr(0x14), Some(Op::Store));
+///
+///
+/// const fn opcodes<const N: usize>(start: u8) -> [u8; N] {
+///     let mut out = [0; N];
+///     multiconst::for_range!{i in 0..N =>
+///         out[i] = start + i as u8 * 2;
+///     }
+///     out
+/// }
+/// ```
+///
+/// ### Mismatched length
+///
+/// This demonstrates that the array length must be the amount of variants.
+///
+/// ```compile_fail
+/// multiconst::multiconst_enum! {
+///     enum Op {
+///         Nop,
+///         Load,
+///     }: [u8; 3] = [3, 5, 8];
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! multiconst_enum {
+    ($($args:tt)*) => {
+        $crate::__::__priv_multiconst_enum_proc_macro!{
+            $crate
+
+            $($args)*
+        }
+    };
+}
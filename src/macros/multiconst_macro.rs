@@ -17,6 +17,20 @@ $(
 )*
 ```
 
+Constants can also be grouped in modules, with this syntax:
+```text
+$(#[$mod_attr:meta])*
+$mod_vis:vis mod $module:ident {
+    // the same syntax as the input of `multiconst`
+    $($contents:tt)*
+}
+```
+which declares a `$module` module containing the constants
+(including the hidden constants that multiconst generates),
+along with a `use super::*;` import
+(which doesn't import items declared inside of functions).
+[example of modules](#module-example)
+
 <span id = "pattern"></span>
 Where `:pattern` arguments can be any of:
 
@@ -26,12 +40,27 @@ which destructures that part of the pattern into a `$binding` constant.
 - ignore pattern: `_`: most useful inside other patterns
 
 - remainder pattern: `$(#[$battr:meta])* $binding:ident @ ..` (only usable in arrays):
-destructures the rest of the matched array into a `$binding` constant.
+destructures the rest of the matched array into a `$binding` constant
+(or into a `&'static [T]` constant when the array pattern matches a slice).
 
 - ignore remainder pattern: `..` (usable in arrays, structs, or tuples):
 ignores the rest of the elements in the matched collection.
 
-- array pattern: `[ $($array_elem:`[`pattern`](#pattern)`),* $(,)? ]`
+- coerced binding pattern:
+`$(#[$battr:meta])* $binding:ident $(@ ..)? : & $referent:ty`
+(only usable as an element of array and tuple patterns, or inside parentheses):
+destructures that part of the pattern into a `$binding` constant of type `&'static $referent`,
+using an unsized coercion (eg: from `[T; N]` to `[T]`).
+Coercing to `&str` requires the `"rust_1_64"` feature,
+and converts from a UTF-8 byte array.
+[example of coerced bindings](#coercion-example)
+
+- array pattern: `[ $($array_elem:`[`pattern`](#pattern)`),* $(,)? ]`:
+destructures arrays, or slices when the type is written as `&[T]`/`&'static [T]`
+(which requires the `"rust_1_61"` feature).
+The constants bound to slice elements are copied out of the slice,
+so those elements must be `Copy`.
+[example of slice destructuring](#slice-destructuring-example)
 
 - tuple pattern: `( $($tuple_elem:`[`pattern`](#pattern)`),* )`:
 
@@ -58,6 +87,16 @@ $struct_name:path (
 )
 ```
 
+- union pattern:
+```text
+unsafe $union_name:path {
+    $(#[$fattr:meta])*
+    $field_name:ident: $union_elem:pattern $(: $type_annotation:ty)?
+    $(,)?
+}
+```
+reads a single field of a union, see [union patterns](#union-patterns).
+
 - `( $pattern:`[`pattern`](#pattern)` )`: a parenthesized pattern
 
 `$vis:vis` can be any visibility modifier,
@@ -67,7 +106,9 @@ it is then used as the visibility of every generated constant.
 
 `$value:expr` can be any const expression (so long as its type is `$type`).
 
-`$field_name:field_name` can be either an untyped integer literal or an identifier.
+`$field_name:field_name` can be either an untyped integer literal or an identifier,
+or in braced struct patterns, a string literal
+(see [string-literal field names](#string-literal-field-names)).
 
 ### Attributes
 
@@ -76,6 +117,52 @@ which allows documenting `pub` constants.
 
 [example of how to do that here](#attrs-example)
 
+`#[cfg(...)]` and `#[cfg_attr(...)]` attributes can be used on patterns
+to conditionally declare those constants,
+and on the `const` item to conditionally declare all of its constants
+(the expression isn't evaluated when all of them are disabled).
+
+The `#[multiconst(...)]` attribute can be used on the `const` item
+to configure what the macro generates,
+it takes these comma-separated arguments:
+
+- `registry = $registry:ident`:
+declares a `$registry` constant (with the same visibility as the other constants)
+of type `[(&'static str, T); N]`,
+which pairs the name of each generated constant with its value.
+`T` is the type of the first constant, every constant must have that type.
+Constants disabled with `#[cfg(...)]` attributes aren't included in the registry.
+[example of the registry argument](#registry-example)
+
+- `provenance_docs`:
+adds a line to the documentation of each generated constant,
+which says what part of `$value` it was destructured from,
+eg: ``Element `2` of `powers_of_two()` `` or ``Field `.limits.max` of `config()` ``.
+
+- `doc = $template:literal`:
+adds a line to the documentation of each generated constant,
+replacing these placeholders in the `$template` string literal:
+    - `{index}`: the position of the array or tuple element that the constant
+    was destructured from, starting from 0,
+    eg: in `[A, _, B]`, `B` has `2` as its `{index}`.
+    For nested patterns this is the position in the innermost array or tuple,
+    and elements after a `..` in a sequence whose length isn't written as a literal
+    have indices like `len - 1`.
+    Constants that aren't destructured from an array or tuple element
+    can't use this placeholder.
+    - `{field}`: the path to the part of `$value` that the constant was destructured from,
+    eg: `[2]` or `.limits.max`.
+    - `{name}`: the name of the constant.
+
+    `{{` and `}}` are used to write `{` and `}`.
+    This argument can be passed multiple times, to add multiple lines.
+    [example of the doc argument](#doc-template-example)
+
+- `debug`:
+makes the macro error with the code that it generates for the `const` item,
+for debugging errors that happen in the generated code.
+The code is still generated, so the errors in it are reported alongside the generated code.
+
 ### Struct patterns
 
 Structs patterns (by default) require the struct to implement
@@ -88,6 +175,55 @@ so you must annotate the array's length.
 
 [example of struct patterns](#example-struct)
 
+<span id = "string-literal-field-names"></span>
+### String-literal field names
+
+Fields of braced struct patterns can be named with string literals,
+eg: `Config{"port": PORT, ..}`,
+which reads the field by the name that the [`FieldType`](derive@crate::FieldType)
+derive gave it, rather than by its name in Rust.
+This allows destructuring fields by the names from the
+[`rename`](derive@crate::FieldType#rename-attr),
+[`alias`](derive@crate::FieldType#alias-attr), and
+[`flatten`](derive@crate::FieldType#flatten-attr) attributes.
+
+These fields are read with methods that the derive generates, so they have these limitations:
+- the struct must derive [`FieldType`](derive@crate::FieldType),
+manual impls of [`FieldType`][trait@crate::FieldType] don't have these methods.
+- the struct can't be `#[repr(packed)]`.
+- structs with bounded generic parameters only have these methods
+with the `"rust_1_61"` feature.
+- renamed fields can't be read by their Rust name in a string literal.
+- the fields are copied out of the struct, so their types must be `Copy`.
+- the field's pattern can only be a constant, or `_`.
+- they can't be used in union patterns,
+nor in struct patterns that contain slice patterns.
+
+Struct patterns with string-literal field names ignore the fields that they don't mention,
+as though they ended with `..`.
+
+Outside of impls, naming a field that the struct doesn't have a method to read
+errors with a message that explains these limitations.
+
+[example of string-literal field names](#example-string-field-names)
+
+<span id = "union-patterns"></span>
+### Union patterns
+
+Union patterns read one field of a union, and otherwise work like struct patterns.
+
+The `unsafe` keyword before the union's name is required,
+because the field is read in an `unsafe` block,
+and reading a union field is only sound if that field was the last one written
+(or is otherwise valid to read as the field's type).
+Only the read of the field is in the `unsafe` block,
+neither the `$value` expression nor the rest of the pattern is,
+so every union pattern requires its own `unsafe` keyword.
+
+Reading union fields in constants requires Rust 1.56.0.
+
+[example of union patterns](#example-union)
+
 # Type Inference
 
 This macro has a limited form of type inference,
@@ -113,9 +249,13 @@ which means that this is allowed:
 
 # Limitations
 
-This macro only supports destructuring tuples, structs, and arrays.
+This macro only supports destructuring tuples, structs, unions, arrays,
+and slices whose type is written as `&[T]`.
+
+Destructuring a slice whose length doesn't match the pattern
+causes a compile-time error when the constants are evaluated.
 
-There are no plans to support destructuring slices or enums.
+There are no plans to support destructuring enums.
 
 # Examples
 
@@ -171,6 +311,93 @@ assert_eq!(END, 23);
 
 ```
 
+<span id = "coercion-example"></span>
+### Coerced bindings
+
+This example demonstrates the `FOO: &[T]` and `FOO @ ..: &[T]` patterns,
+to get constants of unsized reference types.
+
+```rust
+use multiconst::multiconst;
+
+multiconst! {
+    pub const [HEADER, BODY @ ..: &[u8]]: [u8; 6] = *b"\x02hello";
+
+    // parentheses allow using this pattern in struct fields or by itself
+    pub const (NAMES: &[&str]): [&str; 3] = ["foo", "bar", "baz"];
+}
+
+assert_eq!(HEADER, 2);
+assert_eq!(BODY, b"hello");
+assert_eq!(NAMES, ["foo", "bar", "baz"]);
+
+let _: &'static [u8] = BODY;
+let _: &'static [&str] = NAMES;
+
+```
+
+<span id = "str-coercion-example"></span>
+### `&str` coercion
+
+This example demonstrates how byte arrays can be coerced to `&'static str`.
+
+*/
+#[cfg_attr(feature = "rust_1_64", doc = "```rust")]
+#[cfg_attr(not(feature = "rust_1_64"), doc = "```ignore")]
+/**
+use multiconst::multiconst;
+
+multiconst! {
+    // coercing to `&str` requires the "rust_1_64" feature
+    pub const [LEN, NAME @ ..: &str]: [u8; 5] = *b"\x04rust";
+}
+
+assert_eq!(LEN, 4);
+assert_eq!(NAME, "rust");
+
+```
+
+<span id = "slice-destructuring-example"></span>
+### Slice destructuring
+
+This example demonstrates how slices can be destructured,
+which allows destructuring sequences whose length depends on generic parameters
+(types like `[u8; T::LEN]` require the unstable `generic_const_exprs` feature).
+
+The remainder of a slice is a `&'static [T]` constant.
+
+*/
+#[cfg_attr(feature = "rust_1_61", doc = "```rust")]
+#[cfg_attr(not(feature = "rust_1_61"), doc = "```ignore")]
+/**
+use multiconst::associated_multiconst;
+
+trait Packet {
+    const BYTES: &'static [u8];
+}
+
+struct Parsed<T>(T);
+
+impl<T: Packet> Parsed<T> {
+    associated_multiconst! {
+        // destructuring slices requires the "rust_1_61" feature
+        pub const [TAG, LEN, BODY @ .., CHECKSUM]: &[u8] = T::BYTES;
+    }
+}
+
+struct Ping;
+
+impl Packet for Ping {
+    const BYTES: &'static [u8] = &[7, 3, 10, 20, 30, 0xFF];
+}
+
+assert_eq!(Parsed::<Ping>::TAG, 7);
+assert_eq!(Parsed::<Ping>::LEN, 3);
+assert_eq!(Parsed::<Ping>::BODY, [10, 20, 30]);
+assert_eq!(Parsed::<Ping>::CHECKSUM, 0xFF);
+
+```
+
 ### Pseudo-Random number generation
 
 This example demonstrates tuple destructuring
@@ -229,6 +456,72 @@ struct Deriving {
 }
 ```
 
+<span id = "example-string-field-names"></span>
+### String-literal field names example
+
+This example demonstrates destructuring fields by the names that the
+[`FieldType`](derive@crate::FieldType) derive gives them.
+
+*/
+#[cfg_attr(feature = "derive", doc = "```rust")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/**
+use multiconst::{FieldType, multiconst};
+
+multiconst!{
+    const Config{name: NAME, "port": PORT, "timeout": TIMEOUT}: Config = Config{
+        name: "local",
+        server: Server{port: 8080, timeout_secs: 30},
+    };
+}
+
+assert_eq!(NAME, "local");
+assert_eq!(PORT, 8080);
+assert_eq!(TIMEOUT, 30);
+
+#[derive(FieldType)]
+struct Config {
+    name: &'static str,
+    #[field_type(flatten(port, timeout))]
+    server: Server,
+}
+
+#[derive(FieldType)]
+struct Server {
+    port: u16,
+    #[field_type(rename = "timeout")]
+    timeout_secs: u32,
+}
+```
+
+<span id = "example-union"></span>
+### Union example
+
+This example demonstrates reading a union field, in the union that derives the
+[`FieldType`](trait@crate::FieldType) trait.
+
+*/
+#[cfg_attr(all(feature = "derive", feature = "rust_1_61"), doc = "```rust")]
+#[cfg_attr(not(all(feature = "derive", feature = "rust_1_61")), doc = "```ignore")]
+/**
+use multiconst::{FieldType, multiconst};
+
+multiconst!{
+    // `unsafe` is required to read the field,
+    // the value must be valid to read as the type of the field.
+    const unsafe Bits{bytes: [A, B, ..]}: Bits = Bits{int: u32::from_ne_bytes([3, 5, 8, 13])};
+}
+
+assert_eq!(A, 3);
+assert_eq!(B, 5);
+
+#[derive(FieldType, Copy, Clone)]
+union Bits {
+    int: u32,
+    bytes: [u8; 4],
+}
+```
+
 <span id = "example-struct-ty-annot"></span>
 ### Struct example, type annotation
 
@@ -301,6 +594,105 @@ assert_eq!(S, 3);
 ```
 
 
+<span id = "module-example"></span>
+### Module example
+
+This example demonstrates how constants can be declared in a module.
+
+```rust
+use multiconst::multiconst;
+
+multiconst! {
+    /// Limits of things
+    pub mod limits {
+        pub const (MIN_LEN, MAX_LEN): (usize, usize) = LENGTHS;
+        pub const [MIN_DEPTH, MAX_DEPTH]: [u8; _] = [1, 8];
+    }
+}
+
+// items in the parent module are usable in the module
+const LENGTHS: (usize, usize) = (3, 16);
+
+fn main() {
+    assert_eq!((limits::MIN_LEN, limits::MAX_LEN), (3, 16));
+    assert_eq!((limits::MIN_DEPTH, limits::MAX_DEPTH), (1, 8));
+}
+```
+
+<span id = "registry-example"></span>
+### Registry example
+
+This example demonstrates the `#[multiconst(registry = ...)]` attribute,
+which lists the generated constants along with their names.
+
+```rust
+use multiconst::multiconst;
+
+multiconst! {
+    #[multiconst(registry = ALL_LIMITS)]
+    pub const [MIN_LEN, MAX_LEN, MAX_DEPTH]: [u32; _] = [1, 255, 8];
+}
+
+assert_eq!(ALL_LIMITS, [("MIN_LEN", 1), ("MAX_LEN", 255), ("MAX_DEPTH", 8)]);
+
+for (name, value) in ALL_LIMITS.iter() {
+    println!("{}: {}", name, value);
+}
+```
+
+<span id = "doc-template-example"></span>
+### Doc template example
+
+This example demonstrates the `#[multiconst(doc = "...")]` attribute.
+
+```rust
+use multiconst::multiconst;
+
+multiconst! {
+    // `R0` is documented as "Register 0 (`[0]`)",
+    // `R1` is documented as "Register 1 (`[1]`)", and so on.
+    #[multiconst(doc = "Register {index} (`{field}`)")]
+    pub const [R0, R1, R2, R3]: [u16; _] = [0x10, 0x14, 0x18, 0x1C];
+}
+
+assert_eq!([R0, R1, R2, R3], [0x10, 0x14, 0x18, 0x1C]);
+```
+
+### Provenance docs example
+
+This example demonstrates the `#[multiconst(provenance_docs)]` attribute.
+
+```rust
+use multiconst::multiconst;
+
+multiconst! {
+    #[multiconst(provenance_docs)]
+    pub const (
+        /// The smallest length
+        MIN_LEN,
+        [_, SMALL_SIZE, ..],
+    ): (u32, [u64; 4]) = sizes();
+}
+
+// The documentation for `MIN_LEN` is:
+// ```text
+// The smallest length
+//
+// Field `.0` of `sizes()`
+// ```
+assert_eq!(MIN_LEN, 1);
+
+// The documentation for `SMALL_SIZE` is:
+// ```text
+// Field `.1[1]` of `sizes()`
+// ```
+assert_eq!(SMALL_SIZE, 20);
+
+const fn sizes() -> (u32, [u64; 4]) {
+    (1, [10, 20, 30, 40])
+}
+```
+
 */
 #[macro_export]
 macro_rules! multiconst {
@@ -0,0 +1,277 @@
+//! Generators of integer sequences, for destructuring into constants.
+//!
+//! The functions and associated functions in this module are generic over
+//! both the integer type and the length of the returned array,
+//! which means that they can be used in [`multiconst`] with an inferred length.
+//!
+//! The integers are computed at compile-time,
+//! overflowing the integer type causes a compile-time error.
+//!
+//! # Example
+//!
+//! ```rust
+//! use multiconst::{multiconst, seq};
+//!
+//! multiconst! {
+//!     pub const [READ, WRITE, EXECUTE]: [u32; _] = seq::powers_of_two();
+//!
+//!     pub const [ZERO, FIVE, TEN, FIFTEEN]: [i8; _] = seq::Iota::<0, 5>::array();
+//!
+//!     pub const [LOW, MID, HIGH]: [u16; _] = seq::ShiftedBits::<0b11, 4>::array();
+//! }
+//!
+//! assert_eq!([READ, WRITE, EXECUTE], [1, 2, 4]);
+//! assert_eq!([ZERO, FIVE, TEN, FIFTEEN], [0, 5, 10, 15]);
+//! assert_eq!([LOW, MID, HIGH], [0b11, 0b11_0000, 0b11_0000_0000]);
+//!
+//! ```
+//!
+//! [`multiconst`]: crate::multiconst
+
+use crate::for_range;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Integer types that [`powers_of_two`] and [`fibonacci`] can generate arrays of.
+///
+/// This trait is sealed, it can't be implemented outside of `multiconst`.
+pub trait SeqInt<const N: usize>: sealed::Sealed + Sized {
+    #[doc(hidden)]
+    const __POWERS_OF_TWO: [Self; N];
+
+    #[doc(hidden)]
+    const __FIBONACCI: [Self; N];
+}
+
+/// Integer types that [`Iota`] can generate arrays of.
+///
+/// This trait is sealed, it can't be implemented outside of `multiconst`.
+pub trait IotaInt<const START: i128, const STEP: i128, const N: usize>:
+    sealed::Sealed + Sized
+{
+    #[doc(hidden)]
+    const __IOTA: [Self; N];
+}
+
+/// Integer types that [`ShiftedBits`] can generate arrays of.
+///
+/// This trait is sealed, it can't be implemented outside of `multiconst`.
+pub trait ShiftedBitsInt<const BITS: u128, const SHIFT: u32, const N: usize>:
+    sealed::Sealed + Sized
+{
+    #[doc(hidden)]
+    const __SHIFTED_BITS: [Self; N];
+}
+
+/// Generates the `[1, 2, 4, 8, 16, ...]` sequence.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::{multiconst, seq};
+///
+/// multiconst! {
+///     const [A, B, C, D, E]: [u8; _] = seq::powers_of_two();
+/// }
+///
+/// assert_eq!([A, B, C, D, E], [1, 2, 4, 8, 16]);
+///
+/// ```
+pub const fn powers_of_two<T, const N: usize>() -> [T; N]
+where
+    T: SeqInt<N>,
+{
+    T::__POWERS_OF_TWO
+}
+
+/// Generates the fibonacci sequence: `[0, 1, 1, 2, 3, 5, 8, ...]`.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::{multiconst, seq};
+///
+/// multiconst! {
+///     const [_, _, A, B, C, D, E]: [u64; _] = seq::fibonacci();
+/// }
+///
+/// assert_eq!([A, B, C, D, E], [1, 2, 3, 5, 8]);
+///
+/// ```
+pub const fn fibonacci<T, const N: usize>() -> [T; N]
+where
+    T: SeqInt<N>,
+{
+    T::__FIBONACCI
+}
+
+/// Generates the `[START, START + STEP, START + STEP * 2, ...]` sequence.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::{multiconst, seq::Iota};
+///
+/// multiconst! {
+///     const [A, B, C, D]: [i32; _] = Iota::<10, -3>::array();
+/// }
+///
+/// assert_eq!([A, B, C, D], [10, 7, 4, 1]);
+///
+/// ```
+///
+/// # Why is this a type?
+///
+/// The start and step are const parameters (instead of `iota(start, step)` arguments)
+/// because `const fn`s can't call trait methods,
+/// which means that a `const fn` generic over the integer type
+/// can't convert runtime arguments into that type.
+/// The sequence is instead computed in an associated constant of [`IotaInt`].
+pub struct Iota<const START: i128, const STEP: i128>;
+
+impl<const START: i128, const STEP: i128> Iota<START, STEP> {
+    /// Generates an array with the sequence.
+    pub const fn array<T, const N: usize>() -> [T; N]
+    where
+        T: IotaInt<START, STEP, N>,
+    {
+        T::__IOTA
+    }
+}
+
+/// Generates the `[BITS, BITS << SHIFT, BITS << SHIFT * 2, ...]` sequence,
+/// erroring if any bit is shifted out of the integer.
+///
+/// `BITS` is the bit pattern of the elements,
+/// so for signed integers the elements are negative if the highest bit is set,
+/// eg: `ShiftedBits::<0b1000_0001, 0>` generates `-127`s for `i8`.
+///
+/// # Example
+///
+/// ```rust
+/// use multiconst::{multiconst, seq::ShiftedBits};
+///
+/// multiconst! {
+///     const [A, B, C, D]: [u8; _] = ShiftedBits::<0b11, 2>::array();
+/// }
+///
+/// assert_eq!([A, B, C, D], [0b11, 0b1100, 0b11_0000, 0b1100_0000]);
+///
+/// ```
+///
+/// Shifting bits out of the integer is a compile-time error:
+///
+/// ```compile_fail
+/// use multiconst::{multiconst, seq::ShiftedBits};
+///
+/// multiconst! {
+///     // the third element would be `1 << 8`, which doesn't fit in a `u8`.
+///     const [A, B, C]: [u8; _] = ShiftedBits::<1, 4>::array();
+/// }
+/// ```
+pub struct ShiftedBits<const BITS: u128, const SHIFT: u32>;
+
+impl<const BITS: u128, const SHIFT: u32> ShiftedBits<BITS, SHIFT> {
+    /// Generates an array with the sequence.
+    pub const fn array<T, const N: usize>() -> [T; N]
+    where
+        T: ShiftedBitsInt<BITS, SHIFT, N>,
+    {
+        T::__SHIFTED_BITS
+    }
+}
+
+macro_rules! impl_seq_int {
+    ($(($ty:ident, $max_i128:expr))*) => {$(
+        impl sealed::Sealed for $ty {}
+
+        impl<const N: usize> SeqInt<N> for $ty {
+            const __POWERS_OF_TWO: [Self; N] = {
+                let mut arr = [0; N];
+                let mut pow: $ty = 1;
+                for_range!{i in 0..N =>
+                    if i != 0 {
+                        pow *= 2;
+                    }
+                    arr[i] = pow;
+                }
+                arr
+            };
+
+            const __FIBONACCI: [Self; N] = {
+                let mut arr = [0; N];
+                for_range!{i in 0..N =>
+                    arr[i] = if i < 2 {
+                        i as $ty
+                    } else {
+                        arr[i - 1] + arr[i - 2]
+                    };
+                }
+                arr
+            };
+        }
+
+        impl<const START: i128, const STEP: i128, const N: usize>
+            IotaInt<START, STEP, N> for $ty
+        {
+            const __IOTA: [Self; N] = {
+                let mut arr = [0; N];
+                for_range!{i in 0..N =>
+                    let elem = START + STEP * i as i128;
+                    if elem < $ty::MIN as i128 || elem > $max_i128 {
+                        panic!(concat!("an element of the sequence doesn't fit in a `", stringify!($ty), "`"));
+                    }
+                    arr[i] = elem as $ty;
+                }
+                arr
+            };
+        }
+
+        impl<const BITS: u128, const SHIFT: u32, const N: usize>
+            ShiftedBitsInt<BITS, SHIFT, N> for $ty
+        {
+            const __SHIFTED_BITS: [Self; N] = {
+                // the bits are shifted as a `u128`, masked to the width of the integer,
+                // so that signed integers get the bit pattern.
+                let width = core::mem::size_of::<$ty>() as u32 * 8;
+                let mask = if width == 128 { u128::MAX } else { (1 << width) - 1 };
+                if BITS & !mask != 0 {
+                    panic!(concat!("`BITS` doesn't fit in a `", stringify!($ty), "`"));
+                }
+
+                let mut arr = [0; N];
+                for_range!{i in 0..N =>
+                    let shift = SHIFT as u128 * i as u128;
+                    let shifted_out = if shift >= width as u128 {
+                        BITS != 0
+                    } else {
+                        let elem = (BITS << shift) & mask;
+                        arr[i] = elem as $ty;
+                        elem >> shift != BITS
+                    };
+                    if shifted_out {
+                        panic!(concat!("bits were shifted out of the `", stringify!($ty), "`"));
+                    }
+                }
+                arr
+            };
+        }
+    )*};
+}
+
+impl_seq_int! {
+    (u8, u8::MAX as i128)
+    (u16, u16::MAX as i128)
+    (u32, u32::MAX as i128)
+    (u64, u64::MAX as i128)
+    (u128, i128::MAX)
+    (usize, usize::MAX as i128)
+    (i8, i8::MAX as i128)
+    (i16, i16::MAX as i128)
+    (i32, i32::MAX as i128)
+    (i64, i64::MAX as i128)
+    (i128, i128::MAX)
+    (isize, isize::MAX as i128)
+}
@@ -28,7 +28,166 @@ pub struct AssertSameTypes<A: ?Sized, B: ?Sized>(
 where
     A: TypeIdentity<Type = B>;
 
+/// Implemented by the marker types that the `multiconst` macro declares for
+/// the fields that it queries with [`FieldType`](crate::FieldType),
+/// which are named after those fields.
+pub trait FieldMarker {
+    /// The name of the field, eg: `TIdent<(TChars<'f', 'o', 'o', ' ', ' ', ' ', ' ', ' '>,)>`
+    type Name;
+}
+
+/// Queries the field that `M` is the marker type of,
+/// so that the error for a nonexistent field shows the name of the marker type.
+pub struct TField<M>(PhantomData<M>);
+
+/// Implemented by the structs that derive [`FieldType`](crate::FieldType)
+/// for the names of the fields that they have a method to read,
+/// which the `multiconst` macro reads fields named by string literals with.
+#[cfg_attr(
+    feature = "rust_1_78",
+    diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't have a method to read its `{Name}` field",
+        label = "fields named by string literals are read with a method",
+        note = "the `FieldType` derive generates these methods, \
+                except for `#[repr(packed)]` structs, the Rust names of renamed fields, \
+                and structs with bounded generic parameters without the `rust_1_61` feature",
+        note = "`TField<foo>` is the `foo` field in a `multiconst` pattern, \
+                `TIdent`s of `TChars` spell field names in 8-`char` chunks padded with spaces, \
+                and positional fields are `Usize`s"
+    )
+)]
+pub trait FieldAccessor<Name> {
+    /// Evaluated by the `multiconst` macro to assert that the method exists.
+    const EXISTS: () = ();
+}
+
+// `do_not_recommend` makes errors mention `TField<M>` instead of the name of the field
+#[cfg_attr(
+    feature = "rust_1_78",
+    allow(unknown_or_malformed_diagnostic_attributes),
+    diagnostic::do_not_recommend
+)]
+impl<T, M> FieldAccessor<TField<M>> for T
+where
+    M: FieldMarker,
+    T: FieldAccessor<M::Name>,
+{
+}
+
 /// usable for:
 /// - assigning multiple spans to any type
 /// - transforming a type into a path
 pub type Type<T> = T;
+
+/// For the length of the `..` binding in array patterns,
+/// where the array type isn't written as an array.
+///
+/// Arrays shorter than the pattern don't match it,
+/// and the `multiconst` macro asserts that they're long enough
+/// with [`FieldType::ASSERT_EXISTS`](crate::FieldType::ASSERT_EXISTS).
+pub const fn array_rem_length(len: usize, pattern_elems: usize) -> usize {
+    len.saturating_sub(pattern_elems)
+}
+
+/// For the `match` arm of slices whose length doesn't match the pattern
+#[cfg(feature = "rust_1_61")]
+#[allow(clippy::incompatible_msrv)]
+pub const fn slice_length_mismatch() -> ! {
+    panic!("the length of the destructured slice doesn't match the pattern")
+}
+
+/// For the constants bound to slice elements,
+/// which are references to the elements in the pattern.
+#[cfg(feature = "rust_1_61")]
+#[allow(clippy::incompatible_msrv)]
+pub const fn copy_slice_element<T: Copy>(elem: &T) -> T {
+    *elem
+}
+
+/// Calls the `slice_length_mismatch` function,
+/// or errors when the `"rust_1_61"` feature is disabled.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "rust_1_61")]
+macro_rules! __priv_slice_length_mismatch {
+    () => {
+        $crate::__::slice_length_mismatch()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "rust_1_61"))]
+macro_rules! __priv_slice_length_mismatch {
+    () => {
+        $crate::__::compile_error! {"slice patterns require the `rust_1_61` feature"}
+    };
+}
+
+/// Calls the `copy_slice_element` function,
+/// or errors when the `"rust_1_61"` feature is disabled.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "rust_1_61")]
+macro_rules! __priv_copy_slice_element {
+    ($elem:expr) => {
+        $crate::__::copy_slice_element($elem)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "rust_1_61"))]
+macro_rules! __priv_copy_slice_element {
+    ($elem:expr) => {
+        $crate::__::compile_error! {"slice patterns require the `rust_1_61` feature"}
+    };
+}
+
+/// For coercing `[u8; N]` bindings to `&'static str`
+#[cfg(feature = "rust_1_64")]
+#[allow(clippy::incompatible_msrv)]
+pub const fn bytes_to_str(bytes: &'static [u8]) -> &'static str {
+    match core::str::from_utf8(bytes) {
+        Ok(x) => x,
+        Err(_) => panic!("the byte array coerced to `&str` is not valid UTF-8"),
+    }
+}
+
+/// Calls the `bytes_to_str` function,
+/// or errors when the `"rust_1_64"` feature is disabled.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "rust_1_64")]
+macro_rules! __priv_bytes_to_str {
+    ($bytes:expr) => {
+        $crate::__::bytes_to_str($bytes)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "rust_1_64"))]
+macro_rules! __priv_bytes_to_str {
+    ($bytes:expr) => {
+        $crate::__::compile_error! {"coercing to `&str` requires the `rust_1_64` feature"}
+    };
+}
+
+/// Outputs the items when the `"rust_1_61"` feature is enabled,
+/// used for const fns with trait bounds.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "rust_1_61")]
+macro_rules! __priv_if_rust_1_61 {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "rust_1_61"))]
+macro_rules! __priv_if_rust_1_61 {
+    ($($item:item)*) => {};
+}
@@ -3,8 +3,12 @@ mod tests_mod {
     mod derive_tests;
 
     mod assoc_multiconst_tests;
+    mod extension_multiconst_tests;
     mod field_macro_tests;
     mod misc_tests;
+    mod multiconst_enum_tests;
+    #[cfg(feature = "rust_1_61")]
+    mod seq_tests;
     mod slice_destructuring;
     mod struct_destructuring;
     mod tuple_destructuring;
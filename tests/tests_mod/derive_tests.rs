@@ -1,8 +1,8 @@
 use core::cmp::Ordering;
 
-use static_assertions::assert_not_impl_all;
+use static_assertions::{assert_not_impl_all, assert_type_eq_all};
 
-use multiconst::{multiconst, FieldType, Usize};
+use multiconst::{field_name, multiconst, FieldList, FieldType, GetFieldType, Usize, VariantField};
 
 mod fp {
     multiconst::field_name_aliases! {
@@ -261,3 +261,260 @@ fn derive_tuple_part_destructuring() {
         assert_eq!(B, DEF_PTUP.1);
     }
 }
+
+#[test]
+fn derive_enum_field_types() {
+    #[derive(FieldType)]
+    #[allow(dead_code)]
+    pub enum Shape {
+        Circle {
+            radius: f32,
+        },
+        Line((u32, u32), (u32, u32)),
+        #[field_type(priv)]
+        Hidden(u8, #[field_type(pub)] u16),
+        Empty,
+    }
+
+    type Field<V, F> = VariantField<V, F>;
+
+    assert_type_eq_all!(
+        GetFieldType<Shape, Field<field_name!(Circle), field_name!(radius)>>,
+        f32
+    );
+    assert_type_eq_all!(
+        GetFieldType<Shape, Field<field_name!(Line), Usize<0>>>,
+        (u32, u32)
+    );
+    assert_type_eq_all!(
+        GetFieldType<Shape, (Field<field_name!(Line), Usize<1>>, Usize<0>)>,
+        u32
+    );
+    assert_type_eq_all!(
+        GetFieldType<Shape, Field<field_name!(Hidden), Usize<1>>>,
+        u16
+    );
+
+    assert_not_impl_all! {Shape: FieldType<Field<field_name!(Hidden), Usize<0>>>}
+    assert_not_impl_all! {Shape: FieldType<Field<field_name!(Line), Usize<2>>>}
+    assert_not_impl_all! {Shape: FieldType<Field<field_name!(Circle), Usize<0>>>}
+}
+
+#[test]
+fn derive_field_list() {
+    #[derive(FieldType)]
+    #[allow(dead_code)]
+    pub struct Braced<T> {
+        pub r#type: T,
+        hidden: u8,
+        #[field_type(pub)]
+        shown: u16,
+    }
+
+    assert_eq!(Braced::<u32>::FIELD_NAMES, ["type", "shown"]);
+    assert_eq!(Braced::<u32>::FIELD_COUNT, 2);
+    assert_type_eq_all!(
+        <Braced<u32> as FieldList>::Names,
+        (field_name!(type), field_name!(shown))
+    );
+
+    #[derive(FieldType)]
+    #[allow(dead_code)]
+    struct Tupled(u8, u16, u32);
+
+    assert_eq!(Tupled::FIELD_NAMES, ["0", "1", "2"]);
+    assert_eq!(Tupled::FIELD_COUNT, 3);
+    assert_type_eq_all!(<Tupled as FieldList>::Names, (Usize<0>, Usize<1>, Usize<2>));
+
+    #[derive(FieldType)]
+    struct Unit;
+
+    assert_eq!(Unit::FIELD_NAMES, [""; 0]);
+    assert_eq!(Unit::FIELD_COUNT, 0);
+    assert_type_eq_all!(<Unit as FieldList>::Names, ());
+}
+
+// reading union fields in constants requires Rust 1.56
+#[cfg(feature = "rust_1_61")]
+#[test]
+fn derive_union_destructure() {
+    #[derive(FieldType, Copy, Clone)]
+    pub union Bits {
+        pub int: u32,
+        pub bytes: [u8; 4],
+        half: [u16; 2],
+    }
+
+    assert_type_eq_all!(GetFieldType<Bits, field_name!(int)>, u32);
+    assert_type_eq_all!(GetFieldType<Bits, field_name!(bytes)>, [u8; 4]);
+    assert_not_impl_all! {Bits: FieldType<field_name!(half)>}
+
+    const BITS: Bits = Bits {
+        int: u32::from_ne_bytes([3, 5, 8, 13]),
+    };
+
+    multiconst! {
+        const unsafe Bits{bytes: [A, B, ..]}: Bits = BITS;
+        const (C, unsafe Bits{int: D}): (u8, Bits) = (21, BITS);
+        const unsafe Bits{half: [E, F]: [u16; 2]}: Bits = BITS;
+    }
+
+    assert_eq!(A, 3);
+    assert_eq!(B, 5);
+    assert_eq!(C, 21);
+    assert_eq!(D, u32::from_ne_bytes([3, 5, 8, 13]));
+    assert_eq!(E, u16::from_ne_bytes([3, 5]));
+    assert_eq!(F, u16::from_ne_bytes([8, 13]));
+}
+
+#[test]
+fn derive_flatten() {
+    #[derive(FieldType)]
+    #[allow(dead_code)]
+    struct Server {
+        host: &'static str,
+        port: u16,
+        timeouts: [u32; 2],
+    }
+
+    #[derive(FieldType)]
+    struct Config<T> {
+        name: &'static str,
+        #[field_type(flatten(host, port))]
+        server: Server,
+        #[field_type(flatten(0))]
+        extra: (T, u8),
+    }
+
+    assert_type_eq_all!(GetFieldType<Config<u64>, field_name!(host)>, &'static str);
+    assert_type_eq_all!(GetFieldType<Config<u64>, field_name!(port)>, u16);
+    assert_type_eq_all!(GetFieldType<Config<u64>, field_name!(0)>, u64);
+    assert_not_impl_all! {Config<u64>: FieldType<field_name!(timeouts)>}
+
+    assert_eq!(
+        Config::<u64>::FIELD_NAMES,
+        ["name", "server", "host", "port", "extra", "0"]
+    );
+
+    // patterns destructure flattened fields through the field that contains them
+    multiconst! {
+        const Config{
+            name: NAME,
+            server: Server{port: PORT, ..},
+            extra: (EXTRA, _),
+        }: Config<bool> = Config {
+            name: "local",
+            server: Server {
+                host: "localhost",
+                port: 8080,
+                timeouts: [3, 5],
+            },
+            extra: (true, 0),
+        };
+    }
+
+    assert_eq!(NAME, "local");
+    assert_eq!(PORT, 8080);
+    assert!(EXTRA);
+
+    // fields named by string literals are read through the field that contains them
+    multiconst! {
+        const (Config{"port": PORT2, "0": EXTRA2, name: NAME2, ..}, HOSTS): (Config<u8>, u32) = (
+            Config {
+                name: "remote",
+                server: Server {
+                    host: "example.com",
+                    port: 443,
+                    timeouts: [8, 13],
+                },
+                extra: (21, 34),
+            },
+            55,
+        );
+    }
+
+    assert_eq!(PORT2, 443);
+    assert_eq!(EXTRA2, 21);
+    assert_eq!(NAME2, "remote");
+    assert_eq!(HOSTS, 55);
+}
+
+#[test]
+fn derive_rename_alias() {
+    #[derive(FieldType)]
+    struct Token {
+        #[field_type(alias = "kind")]
+        r#type: u8,
+        #[field_type(rename = "text")]
+        internal_text: &'static str,
+        #[field_type(alias = "start")]
+        #[field_type(alias = "0")]
+        offset: usize,
+    }
+
+    assert_type_eq_all!(GetFieldType<Token, field_name!(type)>, u8);
+    assert_type_eq_all!(GetFieldType<Token, field_name!(kind)>, u8);
+    assert_type_eq_all!(GetFieldType<Token, field_name!(text)>, &'static str);
+    assert_type_eq_all!(GetFieldType<Token, field_name!(start)>, usize);
+    assert_type_eq_all!(GetFieldType<Token, field_name!(0)>, usize);
+    // renamed fields aren't queryable by their Rust name
+    assert_not_impl_all! {Token: FieldType<field_name!(internal_text)>}
+
+    assert_eq!(Token::FIELD_NAMES, ["type", "text", "offset"]);
+
+    multiconst! {
+        const Token{r#type: KIND, internal_text: TEXT: &str, offset: OFFSET}: Token = Token {
+            r#type: 3,
+            internal_text: "foo",
+            offset: 5,
+        };
+    }
+
+    assert_eq!(KIND, 3);
+    assert_eq!(TEXT, "foo");
+    assert_eq!(OFFSET, 5);
+
+    // string literals destructure fields by their renamed and aliased names
+    multiconst! {
+        const Token{"kind": KIND2, "text": TEXT2, "start": START, "0": ZEROTH}: Token = Token {
+            r#type: 8,
+            internal_text: "bar",
+            offset: 13,
+        };
+    }
+
+    assert_eq!(KIND2, 8);
+    assert_eq!(TEXT2, "bar");
+    assert_eq!(START, 13);
+    assert_eq!(ZEROTH, 13);
+}
+
+#[test]
+fn derive_packed() {
+    #[derive(FieldType)]
+    #[repr(C, packed)]
+    struct Packed {
+        a: u8,
+        #[field_type(rename = "b")]
+        bb: u32,
+        #[field_type(alias = "second")]
+        c: (u16, u64),
+    }
+
+    assert_type_eq_all!(GetFieldType<Packed, field_name!(a)>, u8);
+    assert_type_eq_all!(GetFieldType<Packed, field_name!(b)>, u32);
+    assert_type_eq_all!(GetFieldType<Packed, field_name!(second)>, (u16, u64));
+
+    multiconst! {
+        const Packed{a: A, bb: B: u32, c: (C0, C1)}: Packed = Packed {
+            a: 3,
+            bb: 5,
+            c: (8, 13),
+        };
+    }
+
+    assert_eq!(A, 3);
+    assert_eq!(B, 5);
+    assert_eq!(C0, 8);
+    assert_eq!(C1, 13);
+}
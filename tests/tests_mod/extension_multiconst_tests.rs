@@ -0,0 +1,35 @@
+use multiconst::extension_multiconst;
+
+extension_multiconst! {
+    /// Limits of `u32`
+    pub trait U32Limits for u32 {
+        const (LO, HI): (u32, u32) = (0, 10);
+
+        #[multiconst(registry = ALL)]
+        const [FIRST, .., #[cfg(any())] NOPE, LAST]: [&'static str; 4] =
+            ["foo", "bar", "baz", "qux"];
+    }
+}
+
+#[test]
+fn extension_multiconst_test() {
+    assert_eq!((u32::LO, u32::HI), (0, 10));
+    assert_eq!((u32::FIRST, u32::LAST), ("foo", "qux"));
+    assert_eq!(<u32 as U32Limits>::ALL, [("FIRST", "foo"), ("LAST", "qux")]);
+}
+
+#[test]
+fn generic_extension_multiconst_test() {
+    extension_multiconst! {
+        trait Sizes for Vec<u64> {
+            const (SIZE, [ELEM_SIZE, ELEM_ALIGN]): (usize, [usize; 2]) = (
+                std::mem::size_of::<Self>(),
+                [std::mem::size_of::<u64>(), std::mem::align_of::<u64>()],
+            );
+        }
+    }
+
+    assert_eq!(Vec::<u64>::SIZE, std::mem::size_of::<Vec<u64>>());
+    assert_eq!(Vec::<u64>::ELEM_SIZE, 8);
+    assert_eq!(Vec::<u64>::ELEM_ALIGN, std::mem::align_of::<u64>());
+}
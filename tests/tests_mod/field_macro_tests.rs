@@ -1,4 +1,6 @@
-use multiconst::{field_name, TChars, TIdent, Usize, __::AssertSameTypes};
+use multiconst::{
+    field_name, field_path, FieldPath, GetFieldType, TChars, TIdent, Usize, __::AssertSameTypes,
+};
 
 #[test]
 fn field_name_test() {
@@ -100,3 +102,154 @@ fn field_name_aliases_test() {
     ass! {hello_world_faei, alias_hello_world_faei}
     ass! {hello_world_faeib, alias_hello_world_faeib}
 }
+
+#[test]
+fn field_path_test() {
+    type P<N, R> = FieldPath<N, R>;
+
+    let _: AssertSameTypes<field_path!(foo), P<field_name!(foo), ()>>;
+    let _: AssertSameTypes<field_path!(3), P<Usize<3>, ()>>;
+    let _: AssertSameTypes<field_path!(foo.bar), P<field_name!(foo), P<field_name!(bar), ()>>>;
+    let _: AssertSameTypes<field_path!(foo.0), P<field_name!(foo), P<Usize<0>, ()>>>;
+    let _: AssertSameTypes<
+        field_path!(foo.0 .1),
+        P<field_name!(foo), P<Usize<0>, P<Usize<1>, ()>>>,
+    >;
+    let _: AssertSameTypes<
+        field_path!(0.1.2 .3.foo),
+        P<Usize<0>, P<Usize<1>, P<Usize<2>, P<Usize<3>, P<field_name!(foo), ()>>>>>,
+    >;
+    let _: AssertSameTypes<field_path!(r#type.10), P<field_name!(type), P<Usize<10>, ()>>>;
+
+    type Nested = (u8, [(u16, core::ops::Range<u32>); 2]);
+    let _: AssertSameTypes<GetFieldType<Nested, field_path!(0)>, u8>;
+    let _: AssertSameTypes<GetFieldType<Nested, field_path!(1.0.1.end)>, u32>;
+
+    mod aliases {
+        multiconst::field_name_aliases! {
+            pub single = foo,
+            pub nested = foo.bar,
+            pub numeric = 1.0.1,
+            pub mixed = 1.0.1.end
+        }
+    }
+
+    let _: AssertSameTypes<aliases::single, field_name!(foo)>;
+    let _: AssertSameTypes<aliases::nested, field_path!(foo.bar)>;
+    let _: AssertSameTypes<aliases::numeric, field_path!(1.0.1)>;
+    let _: AssertSameTypes<GetFieldType<Nested, aliases::mixed>, u32>;
+}
+
+#[cfg(feature = "rust_1_78")]
+#[test]
+fn field_name_str_test() {
+    use multiconst::FieldNameStr;
+
+    fn name<N: FieldNameStr>() -> &'static str {
+        N::NAME
+    }
+
+    assert_eq!(name::<field_name!(0)>(), "0");
+    assert_eq!(name::<field_name!(10)>(), "10");
+    assert_eq!(name::<field_name!(1234567)>(), "1234567");
+    assert_eq!(name::<Usize<{ usize::MAX }>>(), usize::MAX.to_string());
+    assert_eq!(name::<field_name!(foo)>(), "foo");
+    assert_eq!(name::<field_name!(foo_baar)>(), "foo_baar");
+    assert_eq!(name::<field_name!(hello_world_fae)>(), "hello_world_fae");
+    assert_eq!(name::<field_name!(r#match)>(), "match");
+    assert_eq!(name::<field_name!(ñandú_Ωmega_字)>(), "ñandú_Ωmega_字");
+    assert_eq!(
+        name::<field_name!(abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefgh)>(),
+        "abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefg_abcdefgh",
+    );
+}
+
+#[test]
+fn deep_field_path_test() {
+    type Nested = ((((((((((u8, [u16; 2]),),),),),),),),), u32);
+
+    let _: AssertSameTypes<GetFieldType<Nested, field_path!(0.0.0 .0 .0 .0 .0 .0 .0 .0)>, u8>;
+    let _: AssertSameTypes<GetFieldType<Nested, field_path!(0.0.0 .0 .0 .0 .0 .0 .0 .1 .1)>, u16>;
+
+    type Long = (
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u64,
+    );
+    let _: AssertSameTypes<GetFieldType<Long, field_name!(15)>, u64>;
+    let _: AssertSameTypes<
+        GetFieldType<
+            Nested,
+            (
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+                Usize<0>,
+            ),
+        >,
+        u8,
+    >;
+}
+
+#[test]
+fn forwarding_field_type_test() {
+    use core::{mem::ManuallyDrop, ops::Range};
+
+    use multiconst::{FieldType, VariantField};
+
+    struct Table {
+        #[allow(dead_code)]
+        rows: [(u8, &'static str); 4],
+    }
+
+    impl FieldType<field_name!(rows)> for Table {
+        type Type = [(u8, &'static str); 4];
+    }
+
+    type Row = field_path!(rows.2 .1);
+
+    let _: AssertSameTypes<GetFieldType<&'static Table, Row>, &'static str>;
+    let _: AssertSameTypes<GetFieldType<&&Table, Row>, &'static str>;
+    let _: AssertSameTypes<GetFieldType<&mut Table, Row>, &'static str>;
+    let _: AssertSameTypes<GetFieldType<ManuallyDrop<Table>, Row>, &'static str>;
+
+    // forwarding through a field whose type is a reference
+    type Tables = (u32, &'static [Table; 2]);
+    let _: AssertSameTypes<GetFieldType<Tables, field_path!(1.0.rows.0 .0)>, u8>;
+
+    let _: AssertSameTypes<GetFieldType<&Range<u16>, field_name!(end)>, u16>;
+
+    enum Shape {
+        #[allow(dead_code)]
+        Circle { radius: f32 },
+    }
+
+    type Radius = VariantField<field_name!(Circle), field_name!(radius)>;
+
+    impl FieldType<Radius> for Shape {
+        type Type = f32;
+    }
+
+    let _: AssertSameTypes<GetFieldType<&ManuallyDrop<Shape>, Radius>, f32>;
+
+    const _: () = <&[u8; 2] as FieldType<Usize<1>>>::ASSERT_EXISTS;
+}
@@ -10,11 +10,34 @@ multiconst! {
     const FOO: &'static str = "hello";
 }
 
+const LIMITS: (u32, u32) = (3, 5);
+
+multiconst! {
+    /// Limits
+    pub mod limits {
+        pub const (MIN, MAX): (u32, u32) = LIMITS;
+
+        pub(super) const [FIRST, ..]: [&'static str; 2] = ["foo", super::FOO];
+
+        pub mod nested {
+            #[multiconst(registry = ALL)]
+            pub const [A, B]: [u8; _] = [8, 13];
+        }
+    }
+}
+
 #[test]
 fn single_ident() {
     assert_eq!(FOO, "hello");
 }
 
+#[test]
+fn module_test() {
+    assert_eq!((limits::MIN, limits::MAX), (3, 5));
+    assert_eq!(limits::FIRST, "foo");
+    assert_eq!(limits::nested::ALL, [("A", 8), ("B", 13)]);
+}
+
 #[test]
 fn attribute_application_test() {
     {
@@ -37,3 +60,149 @@ fn attribute_application_test() {
         assert_eq!(B, 5);
     }
 }
+
+#[test]
+fn registry_test() {
+    {
+        multiconst! {
+            #[multiconst(registry = ALL)]
+            pub const [A, _, B, r#C]: [u8; 4] = [3, 5, 8, 13];
+        }
+
+        assert_eq!(ALL, [("A", 3), ("B", 8), ("C", 13)]);
+    }
+    {
+        multiconst! {
+            /// docs
+            #[multiconst(registry = SLICES)]
+            const (NAMES: &[&str], [_, REST @ ..: &[&str]]):
+                ([&str; 2], [&str; 3]) = (["foo", "bar"], ["baz", "qux", "quux"]);
+        }
+
+        let _: [(&'static str, &'static [&'static str]); 2] = SLICES;
+        assert_eq!(SLICES, [("NAMES", NAMES), ("REST", REST)]);
+        assert_eq!(REST, ["qux", "quux"]);
+    }
+    {
+        struct Foo;
+
+        impl Foo {
+            multiconst::associated_multiconst! {
+                #[multiconst(registry = LIMITS)]
+                const (MIN, MAX): (u64, u64) = (10, 20);
+            }
+        }
+
+        assert_eq!(Foo::LIMITS, [("MIN", 10), ("MAX", 20)]);
+    }
+}
+
+#[test]
+fn provenance_docs_test() {
+    multiconst! {
+        #[multiconst(provenance_docs)]
+        pub const (
+            /// hello
+            A,
+            [B, C @ ..: &[u8], F],
+            (D, .., E),
+        ): (u8, [u8; 4], (u8, u8, u8)) = (1, *b"\x02\x03\x04\x05", (6, 7, 8));
+    }
+
+    assert_eq!(A, 1);
+    assert_eq!(B, 2);
+    assert_eq!(C, [3, 4]);
+    assert_eq!(F, 5);
+    assert_eq!((D, E), (6, 8));
+}
+
+#[test]
+fn doc_template_test() {
+    multiconst! {
+        /// outer docs
+        #[multiconst(doc = "Register {index}\n\t\"{field}\" \u{1F980}", doc = r#""{name}""#)]
+        #[multiconst(provenance_docs)]
+        pub const [R0, _, R2 @ ..]: [u16; 4] = [0x10, 0x14, 0x18, 0x1C];
+    }
+
+    assert_eq!(R0, 0x10);
+    assert_eq!(R2, [0x18, 0x1C]);
+}
+
+#[test]
+fn cfg_on_bindings_test() {
+    {
+        multiconst! {
+            // the expression doesn't need to compile when the constants are cfg-ed out
+            #[cfg(any())]
+            const (A, [B, C @ ..]): (u32, [u32; 3]) = this_function_does_not_exist();
+        }
+        const A: () = ();
+        assert_eq!(A, ());
+    }
+    {
+        multiconst! {
+            #[multiconst(registry = ALL)]
+            const [
+                A,
+                #[cfg(any())] B,
+                #[cfg(all())] C,
+                #[cfg_attr(all(), cfg(any()))] D,
+                #[cfg_attr(any(), cfg(any()))] E,
+                #[cfg(any())] F @ ..,
+            ]: [u32; 7] = [3, 5, 8, 13, 21, 34, 55];
+        }
+        const B: () = ();
+        const D: () = ();
+        const F: () = ();
+
+        assert_eq!((A, B, C, D, E, F), (3, (), 8, (), 21, ()));
+        assert_eq!(ALL, [("A", 3), ("C", 8), ("E", 21)]);
+    }
+    {
+        multiconst! {
+            // all the constants can be cfg-ed out
+            #[multiconst(registry = ALL)]
+            const (#[cfg(any())] A, #[cfg(any())] B): (u8, u16) = (3, 5);
+        }
+        const A: () = ();
+        const B: () = ();
+
+        assert_eq!((A, B), ((), ()));
+        assert_eq!(ALL, [] as [(&str, u8); 0]);
+    }
+    {
+        struct Foo;
+
+        impl Foo {
+            multiconst::associated_multiconst! {
+                const (A, #[cfg(all())] B, #[cfg(any())] C, D): (u8, u16, u32, u64) =
+                    (3, 5, 8, 13);
+            }
+        }
+        impl Foo {
+            const C: () = ();
+        }
+
+        assert_eq!((Foo::A, Foo::B, Foo::C, Foo::D), (3, 5, (), 13));
+    }
+    {
+        // the hidden constants of these can't collide
+        multiconst! {
+            const (A, #[cfg(all())] B): (u8, u16) = (3, 5);
+            const (A__B, #[cfg(all())] C): (u32, u64) = (8, 13);
+            const (#[cfg(all())] A_B, D): (u8, u8) = (21, 34);
+        }
+
+        assert_eq!((A, B, A__B, C, A_B, D), (3, 5, 8, 13, 21, 34));
+    }
+    {
+        multiconst! {
+            // `cfg_attr`s that don't remove the constant don't make it conditional
+            #[multiconst(registry = ALL)]
+            const [A, #[cfg_attr(any(), doc = "hello")] B, #[cfg(all())] C]: [u8; 3] = [3, 5, 8];
+        }
+
+        assert_eq!(ALL, [("A", 3), ("B", 5), ("C", 8)]);
+    }
+}
@@ -0,0 +1,59 @@
+use multiconst::multiconst_enum;
+
+multiconst_enum! {
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub enum Op {
+        Nop,
+        Load,
+        Store,
+    }: [u8; _] = [3, 5, 8];
+
+    #[derive(Debug, PartialEq)]
+    enum Unrepr {
+        A,
+        B
+    }: [isize; 2] = [-1, 100];
+
+    #[derive(Debug, PartialEq)]
+    enum WithConstLen {
+        X,
+        Y,
+    }: [isize; WITH_CONST_LEN] = [13, 21];
+}
+
+const WITH_CONST_LEN: usize = 2;
+
+#[test]
+fn discriminant_test() {
+    assert_eq!(Op::Nop as u8, 3);
+    assert_eq!(Op::Load as u8, 5);
+    assert_eq!(Op::Store as u8, 8);
+
+    assert_eq!(Unrepr::A as isize, -1);
+    assert_eq!(Unrepr::B as isize, 100);
+
+    assert_eq!(WithConstLen::X as isize, 13);
+    assert_eq!(WithConstLen::Y as isize, 21);
+}
+
+#[test]
+fn all_test() {
+    assert_eq!(Op::ALL, [Op::Nop, Op::Load, Op::Store]);
+    assert_eq!(Unrepr::ALL, [Unrepr::A, Unrepr::B]);
+}
+
+#[test]
+fn from_repr_test() {
+    const LOAD: Option<Op> = Op::from_repr(5);
+    assert_eq!(LOAD, Some(Op::Load));
+
+    assert_eq!(Op::from_repr(2), None);
+    assert_eq!(Op::from_repr(3), Some(Op::Nop));
+    assert_eq!(Op::from_repr(4), None);
+    assert_eq!(Op::from_repr(8), Some(Op::Store));
+
+    assert_eq!(Unrepr::from_repr(-1), Some(Unrepr::A));
+    assert_eq!(Unrepr::from_repr(0), None);
+    assert_eq!(Unrepr::from_repr(100), Some(Unrepr::B));
+}
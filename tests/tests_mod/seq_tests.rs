@@ -0,0 +1,82 @@
+use multiconst::{
+    multiconst,
+    seq::{self, Iota, ShiftedBits},
+};
+
+#[test]
+fn powers_of_two_test() {
+    multiconst! {
+        const [A, B, C, D]: [u8; _] = seq::powers_of_two();
+        const [.., LAST]: [u8; 8] = seq::powers_of_two();
+        const [.., ILAST]: [i16; 15] = seq::powers_of_two();
+    }
+
+    assert_eq!([A, B, C, D], [1, 2, 4, 8]);
+    assert_eq!(LAST, 128);
+    assert_eq!(ILAST, 1 << 14);
+
+    assert_eq!(seq::powers_of_two::<u128, 0>(), [0u128; 0]);
+    assert_eq!(seq::powers_of_two::<u128, 1>(), [1]);
+}
+
+#[test]
+fn fibonacci_test() {
+    multiconst! {
+        const [A, B, C, D, E, F, G]: [i32; _] = seq::fibonacci();
+        const [.., LAST]: [u8; 14] = seq::fibonacci();
+    }
+
+    assert_eq!([A, B, C, D, E, F, G], [0, 1, 1, 2, 3, 5, 8]);
+    assert_eq!(LAST, 233);
+
+    assert_eq!(seq::fibonacci::<u8, 0>(), [0u8; 0]);
+    assert_eq!(seq::fibonacci::<u8, 1>(), [0]);
+    assert_eq!(seq::fibonacci::<u8, 2>(), [0, 1]);
+}
+
+#[test]
+fn iota_test() {
+    multiconst! {
+        const [A, B, C]: [u16; _] = Iota::<100, 20>::array();
+        const [D, E, F]: [i8; _] = Iota::<-128, 127>::array();
+        const [G, H]: [u128; _] = Iota::<{ i128::MAX - 1 }, 1>::array();
+    }
+
+    assert_eq!([A, B, C], [100, 120, 140]);
+    assert_eq!([D, E, F], [-128, -1, 126]);
+    assert_eq!([G, H], [i128::MAX as u128 - 1, i128::MAX as u128]);
+
+    assert_eq!(Iota::<5, 0>::array::<usize, 3>(), [5, 5, 5]);
+    assert_eq!(Iota::<3, -1>::array::<u8, 4>(), [3, 2, 1, 0]);
+}
+
+#[test]
+fn shifted_bits_test() {
+    multiconst! {
+        const [A, B, C]: [u32; _] = ShiftedBits::<0b101, 3>::array();
+        const [D, E]: [i8; _] = ShiftedBits::<0b11, 5>::array();
+        const [F, G]: [u128; _] = ShiftedBits::<1, 127>::array();
+    }
+
+    assert_eq!([A, B, C], [0b101, 0b101_000, 0b101_000_000]);
+    assert_eq!([D, E], [0b11, 0b11 << 5]);
+    assert_eq!([F, G], [1, 1 << 127]);
+
+    assert_eq!(ShiftedBits::<0xFF, 0>::array::<u8, 2>(), [0xFF, 0xFF]);
+
+    // shifting zero by the width of the integer or more is fine
+    assert_eq!(ShiftedBits::<0, 5>::array::<u8, 3>(), [0, 0, 0]);
+    assert_eq!(ShiftedBits::<0, 200>::array::<u128, 2>(), [0, 0]);
+}
+
+#[test]
+fn shifted_bits_signed_test() {
+    assert_eq!(ShiftedBits::<0x80, 0>::array::<i8, 2>(), [-128, -128]);
+    assert_eq!(ShiftedBits::<0b11, 6>::array::<i8, 2>(), [0b11, -64]);
+    assert_eq!(ShiftedBits::<0x8001, 0>::array::<i16, 1>(), [i16::MIN + 1]);
+    assert_eq!(
+        ShiftedBits::<{ 1 << 127 }, 0>::array::<i128, 1>(),
+        [i128::MIN]
+    );
+    assert_eq!(ShiftedBits::<1, 63>::array::<isize, 1>(), [1]);
+}
@@ -122,3 +122,138 @@ fn test_multiple_levels_of_length_inference() {
     assert_eq!(E, 22);
     assert_eq!(H, [30, 31, 32]);
 }
+
+#[test]
+fn test_slice_coercion() {
+    {
+        multiconst! {
+            const [A, B @ ..: &[u8], C]: [u8; 5] = [3, 5, 8, 13, 21];
+        }
+        let _: &'static [u8] = B;
+
+        assert_eq!(A, 3);
+        assert_eq!(B, [5, 8, 13]);
+        assert_eq!(C, 21);
+    }
+    {
+        multiconst! {
+            const [[A, B @ ..: &'static [u8]], C: &[u8]]: [[u8; 3]; _] = [[3, 5, 8], [13, 21, 34]];
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, [5, 8]);
+        assert_eq!(C, [13, 21, 34]);
+    }
+    {
+        multiconst! {
+            const (A: &[u16], B): ([u16; 2], u32) = ([3, 5], 8);
+        }
+        let _: &'static [u16] = A;
+
+        assert_eq!(A, [3, 5]);
+        assert_eq!(B, 8);
+    }
+    {
+        multiconst! {
+            const (A: &[u16]): [u16; 3] = [3, 5, 8];
+        }
+
+        assert_eq!(A, [3, 5, 8]);
+    }
+    {
+        multiconst! {
+            const (A: &dyn core::fmt::Debug, _): (u8, u8) = (3, 5);
+        }
+
+        assert_eq!(std::format!("{:?}", A), "3");
+    }
+}
+
+#[cfg(feature = "rust_1_64")]
+#[test]
+fn test_str_coercion() {
+    multiconst! {
+        const [A, B @ ..: &str]: [u8; 6] = *b"\x05hello";
+    }
+    let _: &'static str = B;
+
+    assert_eq!(A, 5);
+    assert_eq!(B, "hello");
+}
+
+#[cfg(feature = "rust_1_61")]
+#[test]
+fn test_slice_destructuring() {
+    {
+        multiconst! {
+            const [A, B @ .., C]: &[u8] = &[3, 5, 8, 13, 21];
+        }
+        let _: &'static [u8] = B;
+
+        assert_eq!(A, 3);
+        assert_eq!(B, [5, 8, 13]);
+        assert_eq!(C, 21);
+    }
+    {
+        multiconst! {
+            const [A, _]: &'static [&str] = &["foo", "bar"];
+        }
+
+        assert_eq!(A, "foo");
+    }
+    {
+        multiconst! {
+            const (A, [(B, _), (C, [D, E @ ..]), ..]): (u8, &[(u16, [u32; 3])]) = (
+                3,
+                &[(5, [8, 13, 21]), (34, [55, 89, 144]), (233, [377, 610, 987])],
+            );
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, 5);
+        assert_eq!(C, 34);
+        assert_eq!(D, 55);
+        assert_eq!(E, [89, 144]);
+    }
+    {
+        // slices of slices
+        multiconst! {
+            const [[A, B @ ..], .., [.., C]]: &[&[u8]] = &[&[3, 5, 8], &[13], &[21]];
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, [5, 8]);
+        assert_eq!(C, 21);
+    }
+}
+
+#[cfg(feature = "rust_1_61")]
+#[test]
+fn test_generic_slice_destructuring() {
+    trait Sequence {
+        const SEQ: &'static [u32];
+    }
+
+    struct Wrapper<T>(T);
+
+    impl<T: Sequence> Wrapper<T> {
+        multiconst::associated_multiconst! {
+            const [FIRST, REST @ ..]: &[u32] = T::SEQ;
+        }
+    }
+
+    struct Short;
+    impl Sequence for Short {
+        const SEQ: &'static [u32] = &[3];
+    }
+
+    struct Long;
+    impl Sequence for Long {
+        const SEQ: &'static [u32] = &[3, 5, 8, 13];
+    }
+
+    assert_eq!(Wrapper::<Short>::FIRST, 3);
+    assert_eq!(Wrapper::<Short>::REST, [0u32; 0]);
+    assert_eq!(Wrapper::<Long>::FIRST, 3);
+    assert_eq!(Wrapper::<Long>::REST, [5, 8, 13]);
+}
@@ -105,6 +105,98 @@ fn type_alias_remainder_pattern() {
     }
 }
 
+// regression test for the elements around the `..` being read from the wrong positions,
+// when the type isn't written as a tuple
+#[test]
+fn type_alias_many_trailing_patterns() {
+    {
+        type X = (u8, u16, u32, &'static str);
+        mc! {
+            const (A, .., B, C): X = (3, 5, 8, "13");
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, 8);
+        assert_eq!(C, "13");
+    }
+    {
+        type X = (u8, u16, u32, u64, char, &'static str);
+        mc! {
+            const (A, B, .., C, D, E): X = (3, 5, 8, 13, 'c', "34");
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, 5);
+        assert_eq!(C, 13);
+        assert_eq!(D, 'c');
+        assert_eq!(E, "34");
+    }
+    {
+        type X = (u8, u16, u32);
+        mc! {
+            const (A, .., B, C): X = (3, 5, 8);
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, 5);
+        assert_eq!(C, 8);
+    }
+}
+
+#[test]
+fn long_type_alias_remainder_pattern() {
+    {
+        type X = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, &'static str);
+        mc! {
+            const (A, B, .., C): X = (3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 0, "end");
+        }
+
+        assert_eq!(A, 3);
+        assert_eq!(B, 5);
+        assert_eq!(C, "end");
+    }
+    {
+        type X = (
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            &'static str,
+        );
+        mc! {
+            const (.., A, B): X = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, "15");
+        }
+
+        assert_eq!(A, 14);
+        assert_eq!(B, "15");
+    }
+}
+
+#[test]
+fn deeply_nested_pattern() {
+    type X = ((((((((((u8, [u16; 2]),),),),),),),),), u32);
+
+    mc! {
+        const ((((((((((A, [_, B]),),),),),),),),), C): X =
+            ((((((((((3, [5, 8]),),),),),),),),), 13);
+    }
+
+    assert_eq!(A, 3);
+    assert_eq!(B, 8);
+    assert_eq!(C, 13);
+}
+
 #[test]
 fn skipping_one() {
     {
@@ -0,0 +1,37 @@
+use multiconst::FieldType;
+
+#[derive(FieldType)]
+struct Renamed {
+    #[field_type(rename = "c")]
+    a: u8,
+    c: u8,
+}
+
+#[derive(FieldType)]
+struct Aliased {
+    #[field_type(alias = "a")]
+    a: u8,
+}
+
+#[derive(FieldType)]
+struct Flattened {
+    #[field_type(flatten(0))]
+    a: (u8,),
+    #[field_type(alias = "0")]
+    b: u8,
+}
+
+#[derive(FieldType)]
+enum Enum {
+    Foo {
+        #[field_type(alias = "b")]
+        a: u8,
+        b: u8,
+    },
+    // the names of different variants don't conflict
+    Bar {
+        b: u8,
+    },
+}
+
+fn main() {}
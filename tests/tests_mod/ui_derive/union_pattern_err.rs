@@ -0,0 +1,15 @@
+use multiconst::{multiconst, FieldType};
+
+#[derive(FieldType, Copy, Clone)]
+union Bits {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+multiconst! {
+    // every union pattern requires its own `unsafe`
+    const (unsafe Bits{int: A}, Bits{bytes: [B, ..]}): (Bits, Bits) =
+        (Bits{int: 3}, Bits{bytes: [5, 8, 13, 21]});
+}
+
+fn main() {}
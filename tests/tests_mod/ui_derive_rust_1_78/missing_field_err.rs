@@ -0,0 +1,13 @@
+use multiconst::{multiconst, FieldType};
+
+#[derive(FieldType)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+multiconst! {
+    const Point{z: Z, ..}: Point = Point{x: 3, y: 5};
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use multiconst::{multiconst, FieldType};
+
+#[derive(FieldType)]
+struct Token {
+    #[field_type(rename = "text")]
+    internal_text: &'static str,
+    offset: usize,
+}
+
+multiconst! {
+    const Token{"internal_text": TEXT, ..}: Token = Token{internal_text: "foo", offset: 3};
+}
+
+#[derive(FieldType)]
+#[repr(packed)]
+struct Packed {
+    #[field_type(rename = "b")]
+    bb: u32,
+}
+
+multiconst! {
+    const Packed{"b": B}: Packed = Packed{bb: 5};
+}
+
+struct Manual {
+    x: u32,
+}
+
+impl FieldType<multiconst::field_name!(x)> for Manual {
+    type Type = u32;
+}
+
+multiconst! {
+    const Manual{"x": X}: Manual = Manual{x: 8};
+}
+
+fn main() {}
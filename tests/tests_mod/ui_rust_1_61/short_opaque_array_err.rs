@@ -0,0 +1,10 @@
+// the array type is only known after the macro expands
+type Arr = [u8; 1];
+
+multiconst::multiconst! {
+    const [A, B, C @ ..]: Arr = [3];
+
+    const [D, E, ..]: Arr = [5];
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(PartialEq, Debug)]
+pub struct NotCopy(u8);
+
+multiconst::multiconst! {
+    // the constants bound to slice elements are copied out of the slice
+    const [A, ..]: &[NotCopy] = &[NotCopy(3), NotCopy(5)];
+}
+
+fn main() {}
@@ -4,6 +4,12 @@ fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/tests_mod/ui/*err.rs");
 
+    #[cfg(feature = "rust_1_61")]
+    t.compile_fail("tests/tests_mod/ui_rust_1_61/*err.rs");
+
     #[cfg(feature = "derive")]
     t.compile_fail("tests/tests_mod/ui_derive/*err.rs");
+
+    #[cfg(all(feature = "derive", feature = "rust_1_78"))]
+    t.compile_fail("tests/tests_mod/ui_derive_rust_1_78/*err.rs");
 }